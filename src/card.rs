@@ -1,4 +1,6 @@
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
+
+use crate::error::Error;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
@@ -11,7 +13,7 @@ pub enum Suit {
 }
 
 impl FromStr for Suit {
-    type Err = &'static str;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
@@ -19,11 +21,26 @@ impl FromStr for Suit {
             "c" => Ok(Suit::Clubs),
             "d" => Ok(Suit::Diamonds),
             "s" => Ok(Suit::Spades),
-            _ => Err("No match for Suit found"),
+            _ => Err(Error::UnknownSuit {
+                suit: s.to_string(),
+                position: 0,
+            }),
         }
     }
 }
 
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Suit::Hearts => "h",
+            Suit::Clubs => "c",
+            Suit::Diamonds => "d",
+            Suit::Spades => "s",
+        };
+        write!(f, "{}", s)
+    }
+}
+
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Eq, Hash, EnumIter)]
 pub enum Rank {
     Two = 2,
@@ -42,7 +59,7 @@ pub enum Rank {
 }
 
 impl FromStr for Rank {
-    type Err = &'static str;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
@@ -59,11 +76,28 @@ impl FromStr for Rank {
             "q" => Ok(Rank::Queen),
             "k" => Ok(Rank::King),
             "a" => Ok(Rank::Ace),
-            _ => Err("No match for Rank found"),
+            _ => Err(Error::UnknownRank {
+                rank: s.to_string(),
+                position: 0,
+            }),
         }
     }
 }
 
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = match self {
+            Rank::Ten => "T".to_string(),
+            Rank::Jack => "J".to_string(),
+            Rank::Queen => "Q".to_string(),
+            Rank::King => "K".to_string(),
+            Rank::Ace => "A".to_string(),
+            _ => (*self as u8).to_string(),
+        };
+        write!(f, "{}", s)
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct Card {
     pub suit: Suit,
@@ -71,12 +105,16 @@ pub struct Card {
 }
 
 impl Card {
-    pub fn from_cards_str(s: &str) -> Result<Vec<Card>, &'static str> {
+    pub fn from_cards_str(s: &str) -> Result<Vec<Card>, Error> {
         s.chars()
             .collect::<Vec<char>>()
             .chunks(2)
-            .map(|chunk| chunk.iter().collect::<String>())
-            .map(|s| Card::from_str(&s))
+            .enumerate()
+            .map(|(i, chunk)| match chunk {
+                [_] => Err(Error::IncompleteCard { position: i * 2 }),
+                _ => Card::from_str(&chunk.iter().collect::<String>())
+                    .map_err(|e| e.with_offset(i * 2)),
+            })
             .collect()
     }
 
@@ -95,20 +133,27 @@ impl Card {
 }
 
 impl FromStr for Card {
-    type Err = &'static str;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() != 2 {
-            return Err("Wrong size! Expected to receive 2 chars");
+        let chars: Vec<String> = s.chars().map(String::from).collect();
+        if chars.len() != 2 {
+            return Err(Error::WrongCardLength(chars.len()));
         }
 
         Ok(Card {
-            rank: Rank::from_str(&s[..1])?,
-            suit: Suit::from_str(&s[1..])?,
+            rank: Rank::from_str(&chars[0])?,
+            suit: Suit::from_str(&chars[1]).map_err(|e| e.with_offset(1))?,
         })
     }
 }
 
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -124,6 +169,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn cannot_parse_invalid_cards() {
+        assert_eq!(Card::from_str("Acd"), Err(Error::WrongCardLength(3)));
+        assert_eq!(Card::from_str("é"), Err(Error::WrongCardLength(1)));
+        assert_eq!(
+            Card::from_cards_str("AcKhQ"),
+            Err(Error::IncompleteCard { position: 4 })
+        );
+        assert_eq!(
+            Card::from_cards_str("AcXh"),
+            Err(Error::UnknownRank {
+                rank: "X".to_string(),
+                position: 2
+            })
+        );
+        assert_eq!(
+            Card::from_cards_str("AcKhQx"),
+            Err(Error::UnknownSuit {
+                suit: "x".to_string(),
+                position: 5
+            })
+        );
+    }
+
+    #[test]
+    fn can_display_card() {
+        assert_eq!(Card::from_str("Ac").unwrap().to_string(), "Ac");
+        assert_eq!(Card::from_str("9h").unwrap().to_string(), "9h");
+        assert_eq!(Card::from_str("Td").unwrap().to_string(), "Td");
+    }
+
     #[test]
     fn can_get_rank_value() {
         assert_eq!(Rank::Ace as u8, 14);
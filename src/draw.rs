@@ -0,0 +1,451 @@
+use std::{collections::HashMap, iter::once};
+
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+use crate::{
+    card::{Card, Rank, Suit},
+    game::group_cards,
+    hand::validate_cards,
+    rules::Rules,
+    Error, GameType,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum DrawType {
+    /// One suited card short of a flush.
+    FlushDraw,
+    /// Two suited cards short of a flush, with two cards to come.
+    BackdoorFlushDraw,
+    /// Four straight ranks in a row, completed at either end.
+    OpenEnded,
+    /// A single inside rank completes the straight.
+    Gutshot,
+    /// Two inside ranks complete two different straights.
+    DoubleGutshot,
+    /// Three or more ranks complete a straight, more than an open ended draw.
+    Wrap,
+    /// Two more straight ranks are needed, with two cards to come.
+    BackdoorStraightDraw,
+    /// Both hole cards rank above the board and pair none of it.
+    Overcards,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Draw {
+    pub draw_type: DrawType,
+    /// Cards completing the draw, for backdoor draws the cards turning them
+    /// into a draw.
+    pub outs: Vec<Card>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Draws {
+    pub draws: Vec<Draw>,
+}
+
+impl Draws {
+    pub fn has(&self, draw_type: DrawType) -> bool {
+        self.draws.iter().any(|d| d.draw_type == draw_type)
+    }
+
+    /// A flush draw together with a straight draw.
+    pub fn is_combo_draw(&self) -> bool {
+        self.has(DrawType::FlushDraw)
+            && self.draws.iter().any(|d| {
+                matches!(
+                    d.draw_type,
+                    DrawType::OpenEnded
+                        | DrawType::Gutshot
+                        | DrawType::DoubleGutshot
+                        | DrawType::Wrap
+                )
+            })
+    }
+
+    /// Cards completing any draw but a backdoor one, each counted once.
+    pub fn get_outs(&self) -> Vec<Card> {
+        self.draws
+            .iter()
+            .filter(|d| {
+                !matches!(
+                    d.draw_type,
+                    DrawType::BackdoorFlushDraw | DrawType::BackdoorStraightDraw
+                )
+            })
+            .flat_map(|d| d.outs.iter().cloned())
+            .unique()
+            .collect()
+    }
+}
+
+/// Tags the draws of a player on a board with cards to come, for Hold'em, short
+/// deck and Omaha games. Omaha draws have to be completed with exactly two hole
+/// cards and three board cards.
+pub fn get_draws(game_type: GameType, hole: &[Card], board: &[Card]) -> Result<Draws, Error> {
+    let rules = Rules::new(game_type);
+    validate_cards(game_type, &rules, hole, board)?;
+    if board.len() >= game_type.board_cards() {
+        return Err(Error::NoNextStreet);
+    }
+
+    let omaha = game_type.is_omaha();
+    let cards_to_come = game_type.board_cards() - board.len();
+    let unused_cards = rules.get_unused_cards(hole, board);
+    let (hole_suits, hole_ranks) = group_cards(hole);
+    let (board_suits, board_ranks) = group_cards(board);
+
+    let mut draws = get_flush_draws(
+        omaha,
+        cards_to_come,
+        &hole_suits,
+        &board_suits,
+        &unused_cards,
+    );
+    draws.extend(get_straight_draw(
+        &rules,
+        omaha,
+        cards_to_come,
+        &hole_ranks,
+        &board_ranks,
+        &unused_cards,
+    ));
+    if !omaha && hole.len() >= 2 {
+        draws.extend(get_overcards(&hole_ranks, &board_ranks, &unused_cards));
+    }
+    Ok(Draws { draws })
+}
+
+fn get_flush_draws(
+    omaha: bool,
+    cards_to_come: usize,
+    hole_suits: &HashMap<Suit, Vec<Card>>,
+    board_suits: &HashMap<Suit, Vec<Card>>,
+    unused_cards: &[Card],
+) -> Vec<Draw> {
+    Suit::iter()
+        .filter_map(|suit| {
+            let in_hole = hole_suits.get(&suit).map_or(0, Vec::len);
+            let on_board = board_suits.get(&suit).map_or(0, Vec::len);
+            // Board cards of the suit still missing for a flush using the hole cards
+            let missing = match omaha {
+                true if in_hole >= 2 => 3usize.saturating_sub(on_board),
+                false if in_hole >= 1 => 5usize.saturating_sub(in_hole + on_board),
+                _ => return None,
+            };
+            let draw_type = match missing {
+                1 => DrawType::FlushDraw,
+                2 if cards_to_come >= 2 => DrawType::BackdoorFlushDraw,
+                _ => return None,
+            };
+            Some(Draw {
+                draw_type,
+                outs: unused_cards
+                    .iter()
+                    .filter(|c| c.suit == suit)
+                    .cloned()
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+/// Whether the ranks make `straight` using at least one hole card. Omaha hands
+/// use two hole cards and three board cards.
+fn makes_straight(
+    straight: &[Rank],
+    hole_ranks: &[Rank],
+    board_ranks: &[Rank],
+    omaha: bool,
+) -> bool {
+    if omaha {
+        straight.iter().combinations(2).any(|from_hole| {
+            from_hole.iter().all(|r| hole_ranks.contains(r))
+                && straight
+                    .iter()
+                    .filter(|r| !from_hole.contains(r))
+                    .all(|r| board_ranks.contains(r))
+        })
+    } else {
+        straight
+            .iter()
+            .all(|r| hole_ranks.contains(r) || board_ranks.contains(r))
+            && !straight.iter().all(|r| board_ranks.contains(r))
+    }
+}
+
+/// Ranks with cards left that complete a straight on the next card, with the
+/// best straight each of them makes.
+fn get_straight_outs<'a>(
+    straights: &'a [Vec<Rank>],
+    omaha: bool,
+    hole_ranks: &[Rank],
+    board_ranks: &[Rank],
+    unused_cards: &[Card],
+) -> Vec<(Rank, &'a [Rank])> {
+    Rank::iter()
+        .filter(|rank| unused_cards.iter().any(|c| c.rank == *rank))
+        .filter_map(|rank| {
+            let board_ranks: Vec<Rank> = board_ranks.iter().cloned().chain(once(rank)).collect();
+            straights
+                .iter()
+                .find(|s| makes_straight(s, hole_ranks, &board_ranks, omaha))
+                .map(|s| (rank, s.as_slice()))
+        })
+        .collect()
+}
+
+fn get_straight_draw(
+    rules: &Rules,
+    omaha: bool,
+    cards_to_come: usize,
+    hole_ranks: &HashMap<Rank, Vec<Card>>,
+    board_ranks: &HashMap<Rank, Vec<Card>>,
+    unused_cards: &[Card],
+) -> Option<Draw> {
+    let straights = rules.get_straights();
+    let hole_ranks: Vec<Rank> = hole_ranks.keys().cloned().collect();
+    let board_ranks: Vec<Rank> = board_ranks.keys().cloned().collect();
+    if straights
+        .iter()
+        .any(|s| makes_straight(s, &hole_ranks, &board_ranks, omaha))
+    {
+        return None;
+    }
+    let get_outs = |ranks: &[Rank]| -> Vec<Card> {
+        unused_cards
+            .iter()
+            .filter(|c| ranks.contains(&c.rank))
+            .cloned()
+            .collect()
+    };
+
+    let outs = get_straight_outs(&straights, omaha, &hole_ranks, &board_ranks, unused_cards);
+    // Straight ranks already held, both straights of an open ended draw share them
+    let held = |(rank, straight): &(Rank, &[Rank])| -> Vec<Rank> {
+        straight.iter().filter(|r| *r != rank).cloned().collect()
+    };
+    let draw_type = match outs.as_slice() {
+        [] => {
+            if cards_to_come < 2 {
+                return None;
+            }
+            let backdoor_ranks: Vec<Rank> = Rank::iter()
+                .filter(|rank| {
+                    let board_ranks: Vec<Rank> =
+                        board_ranks.iter().cloned().chain(once(*rank)).collect();
+                    !get_straight_outs(&straights, omaha, &hole_ranks, &board_ranks, unused_cards)
+                        .is_empty()
+                })
+                .collect();
+            if backdoor_ranks.is_empty() {
+                return None;
+            }
+            return Some(Draw {
+                draw_type: DrawType::BackdoorStraightDraw,
+                outs: get_outs(&backdoor_ranks),
+            });
+        }
+        [_] => DrawType::Gutshot,
+        [a, b] if held(a).iter().all(|r| held(b).contains(r)) => DrawType::OpenEnded,
+        [_, _] => DrawType::DoubleGutshot,
+        _ => DrawType::Wrap,
+    };
+
+    let out_ranks: Vec<Rank> = outs.iter().map(|(rank, _)| *rank).collect();
+    Some(Draw {
+        draw_type,
+        outs: get_outs(&out_ranks),
+    })
+}
+
+fn get_overcards(
+    hole_ranks: &HashMap<Rank, Vec<Card>>,
+    board_ranks: &HashMap<Rank, Vec<Card>>,
+    unused_cards: &[Card],
+) -> Option<Draw> {
+    let high_board = board_ranks
+        .keys()
+        .max_by(|a, b| a.partial_cmp(b).unwrap())?;
+    let is_paired = hole_ranks.values().any(|cards| cards.len() > 1)
+        || hole_ranks.keys().any(|rank| board_ranks.contains_key(rank));
+    if is_paired || hole_ranks.keys().any(|rank| rank < high_board) {
+        return None;
+    }
+
+    Some(Draw {
+        draw_type: DrawType::Overcards,
+        outs: unused_cards
+            .iter()
+            .filter(|c| hole_ranks.contains_key(&c.rank))
+            .cloned()
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn get_draw_types(draws: &Draws) -> Vec<DrawType> {
+        draws.draws.iter().map(|d| d.draw_type).collect()
+    }
+
+    #[test]
+    fn can_get_flush_draw_and_overcards() {
+        let draws = get_draws(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("AhKh").unwrap(),
+            &Card::from_cards_str("2h7h9c").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_draw_types(&draws),
+            vec![DrawType::FlushDraw, DrawType::Overcards]
+        );
+        assert_eq!(draws.draws[0].outs.len(), 9);
+        assert_eq!(draws.draws[1].outs.len(), 6);
+        assert_eq!(draws.get_outs().len(), 15);
+        assert!(!draws.is_combo_draw());
+    }
+
+    #[test]
+    fn can_get_straight_draws() {
+        for (hole, board, draw_type, outs) in [
+            ("8c9d", "6h7sKc", DrawType::OpenEnded, 8),
+            ("8c9d", "5h7sKc", DrawType::Gutshot, 4),
+            ("5c9d", "7h8sJc", DrawType::DoubleGutshot, 8),
+            ("Ac2d", "3h4s9c", DrawType::Gutshot, 4),
+        ] {
+            let draws = get_draws(
+                GameType::TexasHoldem,
+                &Card::from_cards_str(hole).unwrap(),
+                &Card::from_cards_str(board).unwrap(),
+            )
+            .unwrap();
+
+            assert_eq!(
+                get_draw_types(&draws),
+                vec![draw_type],
+                "{} {}",
+                hole,
+                board
+            );
+            assert_eq!(draws.get_outs().len(), outs, "{} {}", hole, board);
+        }
+    }
+
+    #[test]
+    fn can_get_combo_draw() {
+        let draws = get_draws(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("8h9h").unwrap(),
+            &Card::from_cards_str("6h7hKc").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_draw_types(&draws),
+            vec![DrawType::FlushDraw, DrawType::OpenEnded]
+        );
+        assert!(draws.is_combo_draw());
+        // The five and ten of hearts complete both draws
+        assert_eq!(draws.get_outs().len(), 9 + 8 - 2);
+    }
+
+    #[test]
+    fn can_get_backdoor_draws() {
+        let draws = get_draws(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("Th9h").unwrap(),
+            &Card::from_cards_str("2h7cKs").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_draw_types(&draws),
+            vec![DrawType::BackdoorFlushDraw, DrawType::BackdoorStraightDraw]
+        );
+        assert!(draws.get_outs().is_empty());
+        // Turns giving a straight draw: the sixes, eights, jacks and queens
+        assert_eq!(draws.draws[1].outs.len(), 4 * 4);
+
+        let draws = get_draws(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("Th9h").unwrap(),
+            &Card::from_cards_str("2h7cKsAd").unwrap(),
+        )
+        .unwrap();
+        assert!(draws.draws.is_empty());
+    }
+
+    #[test]
+    fn can_get_short_deck_draws() {
+        // Both A-6-7-8-9 and 6-7-8-9-T complete the straight
+        let draws = get_draws(
+            GameType::ShortdeckHoldem,
+            &Card::from_cards_str("7c8d").unwrap(),
+            &Card::from_cards_str("6h9sKc").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(get_draw_types(&draws), vec![DrawType::OpenEnded]);
+        assert_eq!(draws.get_outs().len(), 8);
+    }
+
+    #[test]
+    fn can_get_omaha_draws() {
+        let draws = get_draws(
+            GameType::Omaha,
+            &Card::from_cards_str("AhKh2c3d").unwrap(),
+            &Card::from_cards_str("5h9hJc").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            get_draw_types(&draws),
+            vec![DrawType::FlushDraw, DrawType::BackdoorStraightDraw]
+        );
+        assert_eq!(draws.get_outs().len(), 9);
+
+        // A single heart in hand does not play with the three on the board
+        let draws = get_draws(
+            GameType::Omaha,
+            &Card::from_cards_str("AhKsQd2c").unwrap(),
+            &Card::from_cards_str("5h9hJh").unwrap(),
+        )
+        .unwrap();
+        assert!(!draws.has(DrawType::FlushDraw));
+
+        let draws = get_draws(
+            GameType::Omaha,
+            &Card::from_cards_str("8c9dTs2h").unwrap(),
+            &Card::from_cards_str("6h7cKd").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(get_draw_types(&draws), vec![DrawType::Wrap]);
+        assert_eq!(draws.get_outs().len(), 13);
+    }
+
+    #[test]
+    fn cannot_get_draws_on_complete_board() {
+        assert_eq!(
+            get_draws(
+                GameType::TexasHoldem,
+                &Card::from_cards_str("AhKh").unwrap(),
+                &Card::from_cards_str("2h7h9c3d4s").unwrap(),
+            ),
+            Err(Error::NoNextStreet)
+        );
+        assert_eq!(
+            get_draws(
+                GameType::TexasHoldem,
+                &Card::from_cards_str("AhKh").unwrap(),
+                &[Card::from_str("Ah").unwrap()],
+            ),
+            Err(Error::DuplicateCard(Card::from_str("Ah").unwrap()))
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use std::fmt;
+
+use crate::{
+    card::{Card, Rank},
+    GameType, LowStraight,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Error {
+    /// Unknown rank character at `position` of the parsed string.
+    UnknownRank {
+        rank: String,
+        position: usize,
+    },
+    /// Unknown suit character at `position` of the parsed string.
+    UnknownSuit {
+        suit: String,
+        position: usize,
+    },
+    /// A card needs exactly a rank and a suit.
+    WrongCardLength(usize),
+    /// The card at `position` has a rank but no suit.
+    IncompleteCard {
+        position: usize,
+    },
+    UnknownGameType(String),
+    /// A part of a range that cannot be parsed, `token` being the piece of it at
+    /// fault.
+    InvalidRange {
+        part: String,
+        token: String,
+        reason: &'static str,
+    },
+    /// The card is not dealt in the game, like a two in short deck.
+    CardNotInDeck(Card),
+    /// The same card is held twice, by players, the board or the dead cards.
+    DuplicateCard(Card),
+    WrongHandSize {
+        game_type: GameType,
+        player: usize,
+        cards: usize,
+    },
+    TooManyBoardCards {
+        game_type: GameType,
+        cards: usize,
+    },
+    /// Ranges hold two card combos and need a game with two hole cards.
+    RangeNotSupported(GameType),
+    /// Every combo of the player's range uses known or dead cards.
+    EmptyRange {
+        player: usize,
+    },
+    /// The ranges cannot be dealt without two players sharing a card.
+    ConflictingRanges,
+    /// A table needs at least one player.
+    NoPlayers,
+    /// A hand is made of five cards, only this many can be used.
+    NotEnoughCards(usize),
+    /// More cards are needed than are left in the deck.
+    DeckExhausted {
+        needed: usize,
+        available: usize,
+    },
+    /// Every community card is dealt already, or the game has none.
+    NoNextStreet,
+    /// Outs are counted with `expected` community cards to come.
+    WrongMissingBoardCards {
+        expected: usize,
+        missing: usize,
+    },
+    /// Players with unknown cards or ranges do not have a single outcome per card.
+    UnknownCards,
+    /// Boards are analysed from the flop on, only this many community cards are dealt.
+    NoFlop(usize),
+    /// Hand strength is measured against the holdings of a single opponent.
+    NotHeadsUp(usize),
+    /// The ranking of the rules misses a hand combination or holds one twice.
+    InvalidRanking,
+    /// The low straight of the rules does not start at the lowest rank of the deck.
+    InvalidLowStraight {
+        low_straight: LowStraight,
+        lowest_rank: Rank,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownRank { rank, position } => {
+                write!(f, "Unknown rank `{}` at position {}", rank, position)
+            }
+            Error::UnknownSuit { suit, position } => {
+                write!(f, "Unknown suit `{}` at position {}", suit, position)
+            }
+            Error::WrongCardLength(length) => {
+                write!(f, "Wrong size! Expected 2 chars for a card, got {}", length)
+            }
+            Error::IncompleteCard { position } => {
+                write!(f, "Card at position {} is missing its suit", position)
+            }
+            Error::UnknownGameType(game_type) => write!(f, "Unknown game type `{}`", game_type),
+            Error::InvalidRange {
+                part,
+                token,
+                reason,
+            } => write!(f, "Invalid range `{}`: {}, got `{}`", part, reason, token),
+            Error::CardNotInDeck(card) => write!(f, "{} is not in the deck of the game", card),
+            Error::DuplicateCard(card) => write!(f, "{} is used more than once", card),
+            Error::WrongHandSize {
+                game_type,
+                player,
+                cards,
+            } => write!(
+                f,
+                "{:?} allows at most {} cards per player, player {} has {}",
+                game_type,
+                game_type.hole_cards(),
+                player + 1,
+                cards
+            ),
+            Error::TooManyBoardCards { game_type, cards } => write!(
+                f,
+                "{:?} allows at most {} community cards, got {}",
+                game_type,
+                game_type.board_cards(),
+                cards
+            ),
+            Error::RangeNotSupported(game_type) => write!(
+                f,
+                "{:?} does not support ranges, they hold two card combos",
+                game_type
+            ),
+            Error::EmptyRange { player } => write!(
+                f,
+                "Every combo in the range of player {} uses known or dead cards",
+                player + 1
+            ),
+            Error::ConflictingRanges => {
+                write!(f, "Ranges cannot be dealt without sharing cards")
+            }
+            Error::NoPlayers => write!(f, "There are no players at the table"),
+            Error::NotEnoughCards(cards) => {
+                write!(f, "A hand needs 5 cards, only {} can be used", cards)
+            }
+            Error::DeckExhausted { needed, available } => write!(
+                f,
+                "{} cards are needed but only {} are left in the deck",
+                needed, available
+            ),
+            Error::NoNextStreet => write!(f, "There are no community cards left to deal"),
+            Error::WrongMissingBoardCards { expected, missing } => write!(
+                f,
+                "Expected {} community cards to come, {} are missing",
+                expected, missing
+            ),
+            Error::UnknownCards => write!(f, "Every card of the players has to be known"),
+            Error::NoFlop(cards) => {
+                write!(f, "The flop has to be dealt, got {} community cards", cards)
+            }
+            Error::NotHeadsUp(players) => {
+                write!(
+                    f,
+                    "Expected a player and one opponent, got {} players",
+                    players
+                )
+            }
+            Error::InvalidRanking => {
+                write!(f, "The ranking has to hold every hand combination once")
+            }
+            Error::InvalidLowStraight {
+                low_straight,
+                lowest_rank,
+            } => write!(
+                f,
+                "{:?} is not the lowest straight of a deck from {:?} up",
+                low_straight, lowest_rank
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Error {
+    /// Moves the position of a parsing error by `offset` characters, for errors
+    /// of a card inside a longer string.
+    pub(crate) fn with_offset(self, offset: usize) -> Error {
+        match self {
+            Error::UnknownRank { rank, position } => Error::UnknownRank {
+                rank,
+                position: position + offset,
+            },
+            Error::UnknownSuit { suit, position } => Error::UnknownSuit {
+                suit,
+                position: position + offset,
+            },
+            Error::IncompleteCard { position } => Error::IncompleteCard {
+                position: position + offset,
+            },
+            error => error,
+        }
+    }
+}
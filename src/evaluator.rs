@@ -0,0 +1,493 @@
+//! Hand evaluation on 64-bit card masks. Every suit takes 16 bits of the mask
+//! with one bit per rank, `Rank::Two` being the lowest one.
+
+use crate::{
+    card::{Card, Suit},
+    game::{HandCombination, HandStrength},
+    rules::{LowStraight, Rules},
+};
+
+pub type CardMask = u64;
+
+const RANK_BITS: u32 = 0x1fff;
+
+/// Highest rank of the best straight for every combination of 13 rank bits, 0
+/// when there is none. Straights with a low ace depend on the rules and are left
+/// out.
+static STRAIGHTS: [u8; 8192] = build_straights();
+
+const fn build_straights() -> [u8; 8192] {
+    let mut straights = [0u8; 8192];
+    let mut ranks = 0;
+    while ranks < 8192 {
+        let mut high = 12;
+        while high >= 4 {
+            let straight = 0b11111 << (high - 4);
+            if ranks & straight == straight {
+                straights[ranks] = high as u8 + 2;
+                break;
+            }
+            high -= 1;
+        }
+        ranks += 1;
+    }
+    straights
+}
+
+/// The five highest ranks of every combination of 13 rank bits with at least five
+/// of them, 0 for the others. Indexed by the ranks of one suit, it finds flushes
+/// and their tie breakers at once.
+static FLUSHES: [u16; 8192] = build_flushes();
+
+const fn build_flushes() -> [u16; 8192] {
+    let mut flushes = [0u16; 8192];
+    let mut ranks = 0;
+    while ranks < 8192 {
+        if (ranks as u32).count_ones() >= 5 {
+            let mut highest = ranks;
+            while (highest as u32).count_ones() > 5 {
+                highest &= highest - 1;
+            }
+            flushes[ranks] = highest as u16;
+        }
+        ranks += 1;
+    }
+    flushes
+}
+
+/// Rank bits of every straight with a low ace and its highest rank, indexed by
+/// `LowStraight`.
+static LOW_STRAIGHTS: [(u32, u32); 2] = [
+    build_low_straight(LowStraight::AceToFive),
+    build_low_straight(LowStraight::AceToNine),
+];
+
+const fn build_low_straight(low_straight: LowStraight) -> (u32, u32) {
+    let ranks = low_straight.ranks();
+    let mut bits = 0;
+    let mut i = 0;
+    while i < ranks.len() {
+        bits |= 1 << (ranks[i] as u32 - 2);
+        i += 1;
+    }
+    (bits, ranks[4] as u32)
+}
+
+pub fn card_to_mask(card: &Card) -> CardMask {
+    let suit = match card.suit {
+        Suit::Hearts => 0,
+        Suit::Clubs => 1,
+        Suit::Diamonds => 2,
+        Suit::Spades => 3,
+    };
+    1 << (suit * 16 + card.rank as u64 - 2)
+}
+
+pub fn cards_to_mask(cards: &[Card]) -> CardMask {
+    cards.iter().fold(0, |mask, c| mask | card_to_mask(c))
+}
+
+/// Single card masks of the cards in `mask`, from the lowest bit up.
+pub(crate) fn get_card_masks(mask: CardMask) -> impl Iterator<Item = CardMask> + Clone {
+    let mut rest = mask;
+    std::iter::from_fn(move || {
+        let card = rest & rest.wrapping_neg();
+        rest &= !card;
+        Some(card).filter(|card| *card != 0)
+    })
+}
+
+/// Masks of every `count` cards of `mask`, walking all of its submasks. Only meant
+/// for the few cards of a board or a hand.
+pub(crate) fn get_mask_combinations(
+    mask: CardMask,
+    count: u32,
+) -> impl Iterator<Item = CardMask> + Clone {
+    let mut next = Some(mask);
+    std::iter::from_fn(move || loop {
+        let submask = next?;
+        next = if submask == 0 {
+            None
+        } else {
+            Some((submask - 1) & mask)
+        };
+        if submask.count_ones() == count {
+            return Some(submask);
+        }
+    })
+}
+
+/// Bit of the highest rank, `ranks` must not be empty.
+fn highest_rank(ranks: u32) -> u32 {
+    debug_assert!(ranks != 0, "no rank to pick");
+    1 << (31 - ranks.leading_zeros())
+}
+
+/// Keeps the `count` highest ranks.
+fn highest_ranks(ranks: u32, count: u32) -> u32 {
+    let mut ranks = ranks;
+    while ranks.count_ones() > count {
+        ranks &= ranks - 1;
+    }
+    ranks
+}
+
+/// Scores the best five card hand in `mask`. Points are the position of the hand
+/// combination in the ranking followed by the ranks that break ties, so they can
+/// be compared directly.
+pub fn evaluate(mask: CardMask, rules: &Rules) -> HandStrength {
+    let suits = [
+        mask as u32 & RANK_BITS,
+        (mask >> 16) as u32 & RANK_BITS,
+        (mask >> 32) as u32 & RANK_BITS,
+        (mask >> 48) as u32 & RANK_BITS,
+    ];
+    let ranks = suits[0] | suits[1] | suits[2] | suits[3];
+    let (low_straight, low_straight_high) = LOW_STRAIGHTS[rules.low_straight as usize];
+    let get_straight = |ranks: u32| -> u32 {
+        match STRAIGHTS[ranks as usize] {
+            0 if ranks & low_straight == low_straight => low_straight_high,
+            high => high as u32,
+        }
+    };
+
+    let flush = suits.iter().find(|s| FLUSHES[**s as usize] != 0).cloned();
+    let quads = suits[0] & suits[1] & suits[2] & suits[3];
+    let three_or_more = ((suits[0] & suits[1]) | (suits[2] & suits[3]))
+        & ((suits[0] & suits[2]) | (suits[1] & suits[3]));
+    let trips = three_or_more & !quads;
+    let pairs = (ranks ^ suits[0] ^ suits[1] ^ suits[2] ^ suits[3]) & !quads;
+
+    let order = &rules.ranking;
+
+    for (i, hand_combination) in order.iter().enumerate() {
+        let tie_breaker = match hand_combination {
+            HandCombination::RoyalFlush => flush.filter(|f| get_straight(*f) == 14).map(|_| 0),
+            HandCombination::StraightFlush => flush.map(get_straight).filter(|high| *high != 0),
+            HandCombination::Quads if quads != 0 => {
+                Some(quads << 13 | highest_ranks(ranks & !quads, 1))
+            }
+            HandCombination::FullHouse if trips != 0 => {
+                let trip = highest_rank(trips);
+                let pair = (trips & !trip) | pairs;
+                if pair != 0 {
+                    Some(trip << 13 | highest_rank(pair))
+                } else {
+                    None
+                }
+            }
+            HandCombination::Flush => flush.map(|f| FLUSHES[f as usize] as u32),
+            HandCombination::Straight => Some(get_straight(ranks)).filter(|high| *high != 0),
+            HandCombination::TreeOfAKind if trips != 0 => {
+                let trip = highest_rank(trips);
+                Some(trip << 13 | highest_ranks(ranks & !trip, 2))
+            }
+            HandCombination::TwoPairs if pairs.count_ones() >= 2 => {
+                let two_pairs = highest_ranks(pairs, 2);
+                Some(two_pairs << 13 | highest_ranks(ranks & !two_pairs, 1))
+            }
+            HandCombination::OnePair if pairs != 0 => {
+                Some(pairs << 13 | highest_ranks(ranks & !pairs, 3))
+            }
+            HandCombination::HighCards => Some(highest_ranks(ranks, 5)),
+            _ => None,
+        };
+
+        if let Some(tie_breaker) = tie_breaker {
+            return HandStrength {
+                hand_combination: *hand_combination,
+                points: ((order.len() - i) as u64) << 26 | tie_breaker as u64,
+                low: None,
+            };
+        }
+    }
+
+    unreachable!("high cards always match")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use itertools::Itertools;
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::{
+        card::Rank, game::get_hand_strength, hand::sort_high_cards, rules::Ante,
+        strength::short_deck::get_short_deck_hand, GameType,
+    };
+
+    /// Hand combination position and ranks of the cards `get_hand_strength` picks.
+    type ReferenceKey = (usize, Vec<Rank>);
+
+    /// Sorts random hands by the hand combination and cards `get_hand_strength`
+    /// picks and checks that the lookup evaluator orders them the same way. The
+    /// picked cards come in the order of each per-combination function, so they are
+    /// put in `HandRank` order first.
+    fn assert_same_order(game_type: GameType, rules: Rules, card_count: usize) {
+        let mut rng = StdRng::seed_from_u64(card_count as u64);
+        let mut deck = rules.get_deck();
+        let mut hands: Vec<(ReferenceKey, HandStrength, Vec<Card>)> = (0..20000)
+            .map(|_| {
+                deck.shuffle(&mut rng);
+                let cards = deck[..card_count].to_vec();
+                let (expected, mut expected_cards) =
+                    get_hand_strength(game_type, &cards[2..], &cards[..2], &rules);
+                let strength = evaluate(cards_to_mask(&cards), &rules);
+                assert_eq!(
+                    strength.hand_combination, expected.hand_combination,
+                    "{:?}",
+                    cards
+                );
+                sort_high_cards(&mut expected_cards, expected.hand_combination);
+                let position = rules
+                    .ranking
+                    .iter()
+                    .rev()
+                    .position(|hc| *hc == expected.hand_combination)
+                    .unwrap();
+                let ranks = expected_cards.iter().map(|c| c.rank).collect();
+                ((position, ranks), strength, cards)
+            })
+            .collect();
+        hands.sort_by(|(key, _, _), (next_key, _, _)| key.partial_cmp(next_key).unwrap());
+
+        for pair in hands.windows(2) {
+            let ((key, strength, cards), (next_key, next_strength, next_cards)) =
+                (&pair[0], &pair[1]);
+            assert_eq!(
+                key.partial_cmp(next_key),
+                strength.points.partial_cmp(&next_strength.points),
+                "{:?} {:?}",
+                cards,
+                next_cards
+            );
+        }
+    }
+
+    /// Checks that the points order short deck `hands` like the reference evaluator,
+    /// with the same points for hands the reference ranks the same.
+    fn assert_same_order_as_short_deck_reference(
+        rules: &Rules,
+        hands: impl Iterator<Item = Vec<Card>>,
+    ) {
+        let mut points_by_reference: HashMap<u64, u64> = HashMap::new();
+        for cards in hands {
+            let (hand_combination, reference_points) = get_short_deck_hand(&cards, rules);
+            let strength = evaluate(cards_to_mask(&cards), rules);
+            assert_eq!(strength.hand_combination, hand_combination, "{:?}", cards);
+            let points = points_by_reference
+                .entry(reference_points)
+                .or_insert(strength.points);
+            assert_eq!(*points, strength.points, "{:?}", cards);
+        }
+
+        let points: Vec<(u64, u64)> = points_by_reference.into_iter().sorted().collect();
+        for pair in points.windows(2) {
+            assert!(pair[0].1 < pair[1].1, "{:?}", pair);
+        }
+    }
+
+    #[test]
+    fn can_get_straights() {
+        assert_eq!(STRAIGHTS[0b1_1111_0000_0000], 14);
+        assert_eq!(STRAIGHTS[0b1_0000_0000_1111], 0);
+        assert_eq!(STRAIGHTS[0b1_0000_0001_1111], 6);
+        assert_eq!(STRAIGHTS[0b0_1110_1111_0000], 0);
+    }
+
+    #[test]
+    fn can_get_flushes() {
+        assert_eq!(FLUSHES[0b1_1111_0000_0000], 0b1_1111_0000_0000);
+        assert_eq!(FLUSHES[0b1_0101_0101_0101], 0b1_0101_0101_0000);
+        assert_eq!(FLUSHES[0b0_0000_0000_1111], 0);
+    }
+
+    #[test]
+    fn can_evaluate_hands() {
+        let rules = Rules::new(GameType::TexasHoldem);
+        for (cards, hand_combination) in [
+            ("AhKhQhJhTh2c3d", HandCombination::RoyalFlush),
+            ("5h4h3h2hAh9c9d", HandCombination::StraightFlush),
+            ("9c9d9h9s2c3d4h", HandCombination::Quads),
+            ("9c9d9hAsAc3d4h", HandCombination::FullHouse),
+            ("9c9d9hAsAcAd4h", HandCombination::FullHouse),
+            ("2c4c6c8cTcAsAd", HandCombination::Flush),
+            ("Ac2d3h4s5c9d9h", HandCombination::Straight),
+            ("9c9d9hAsKc3d4h", HandCombination::TreeOfAKind),
+            ("9c9dAhAsKcKd4h", HandCombination::TwoPairs),
+            ("9c9dAhQsKc3d4h", HandCombination::OnePair),
+            ("9c8dAhQsKc3d4h", HandCombination::HighCards),
+        ] {
+            assert_eq!(
+                evaluate(cards_to_mask(&Card::from_cards_str(cards).unwrap()), &rules)
+                    .hand_combination,
+                hand_combination
+            );
+        }
+    }
+
+    #[test]
+    fn can_compare_hands() {
+        let rules = Rules::new(GameType::TexasHoldem);
+        let points = |cards: &str| {
+            evaluate(cards_to_mask(&Card::from_cards_str(cards).unwrap()), &rules).points
+        };
+        for (worse, better) in [
+            ("5h4h3h2hAh", "6h5h4h3h2h"),
+            ("Ac2d3h4s5c", "2c3d4h5s6c"),
+            ("AhAdKcKs2d", "AhAdKcKsQd"),
+        ] {
+            assert!(points(worse) < points(better), "{} {}", worse, better);
+        }
+        assert_eq!(points("AhAdKcKsQd2c3c"), points("AcAsKhKdQh4d2d"));
+    }
+
+    #[test]
+    fn can_evaluate_hands_without_kickers() {
+        let rules = Rules::new(GameType::TexasHoldem);
+        let evaluate_cards =
+            |cards: &str| evaluate(cards_to_mask(&Card::from_cards_str(cards).unwrap()), &rules);
+
+        let quads = evaluate_cards("AhAdAcAs");
+        let two_pairs = evaluate_cards("KhKdQcQs");
+        assert_eq!(quads.hand_combination, HandCombination::Quads);
+        assert_eq!(two_pairs.hand_combination, HandCombination::TwoPairs);
+        assert!(quads.points < evaluate_cards("AhAdAcAs2c").points);
+        assert!(two_pairs.points < evaluate_cards("KhKdQcQs2c").points);
+        assert_eq!(
+            evaluate(0, &rules).hand_combination,
+            HandCombination::HighCards
+        );
+    }
+
+    #[test]
+    fn can_get_mask_combinations() {
+        let mask = cards_to_mask(&Card::from_cards_str("AhKd7c2s9h").unwrap());
+
+        assert_eq!(get_mask_combinations(mask, 3).count(), 10);
+        assert!(get_mask_combinations(mask, 3).all(|m| m.count_ones() == 3 && m & !mask == 0));
+        assert_eq!(
+            get_mask_combinations(mask, 5).collect::<Vec<_>>(),
+            vec![mask]
+        );
+        assert_eq!(get_card_masks(mask).fold(0, |m, card| m | card), mask);
+        assert_eq!(get_card_masks(mask).count(), 5);
+    }
+
+    #[test]
+    fn can_evaluate_short_deck_hands() {
+        let rules = Rules::new(GameType::ShortdeckHoldem);
+        let wheel = evaluate(
+            cards_to_mask(&Card::from_cards_str("Ac6d7h8s9c").unwrap()),
+            &rules,
+        );
+        let ten_high = evaluate(
+            cards_to_mask(&Card::from_cards_str("Tc6d7h8s9c").unwrap()),
+            &rules,
+        );
+        assert_eq!(wheel.hand_combination, HandCombination::Straight);
+        assert!(wheel.points < ten_high.points);
+    }
+
+    #[test]
+    fn has_same_order_as_get_hand_strength() {
+        let rules = Rules::new(GameType::TexasHoldem);
+        assert_same_order(GameType::TexasHoldem, rules.clone(), 7);
+        assert_same_order(GameType::TexasHoldem, rules, 5);
+    }
+
+    #[test]
+    fn has_same_order_as_get_hand_strength_short_deck() {
+        assert_same_order(
+            GameType::ShortdeckHoldem,
+            Rules::short_deck(Ante::ButtonBlind),
+            7,
+        );
+        assert_same_order(
+            GameType::ShortdeckHoldem,
+            Rules::short_deck(Ante::AnteOnly),
+            7,
+        );
+    }
+
+    #[test]
+    fn has_same_order_as_short_deck_reference() {
+        for rules in [
+            Rules::short_deck(Ante::ButtonBlind),
+            Rules::short_deck(Ante::AnteOnly),
+        ] {
+            let mut rng = StdRng::seed_from_u64(36);
+            let mut deck = rules.get_deck();
+            let hands = (0..50000).map(|_| {
+                deck.shuffle(&mut rng);
+                deck[..7].to_vec()
+            });
+            assert_same_order_as_short_deck_reference(&rules, hands);
+        }
+    }
+
+    /// One seven card short deck hand for every rank multiset, without a flush and
+    /// with each set of five or more of its ranks suited. Ranks and the suited
+    /// ranks decide a hand, so this covers every hand the deck can deal.
+    fn get_short_deck_hand_classes() -> Vec<Vec<Card>> {
+        let ranks: Vec<Rank> = Rank::iter().filter(|r| *r >= Rank::Six).collect();
+        let mut hands = vec![];
+        for ranks in ranks.into_iter().combinations_with_replacement(7) {
+            let counts = ranks.iter().counts();
+            if counts.values().any(|count| *count > 4) {
+                continue;
+            }
+            // Ranks are grouped, so dealing suits in turn gives no flush
+            hands.push(
+                ranks
+                    .iter()
+                    .zip(Suit::iter().cycle())
+                    .map(|(rank, suit)| Card { rank: *rank, suit })
+                    .collect(),
+            );
+
+            let distinct: Vec<Rank> = ranks.iter().cloned().unique().collect();
+            for size in 5..=distinct.len() {
+                for suited in distinct.iter().cloned().combinations(size) {
+                    let mut rest = ranks.clone();
+                    for rank in &suited {
+                        let i = rest.iter().position(|r| r == rank).unwrap();
+                        rest.remove(i);
+                    }
+                    let other_suits = [Suit::Clubs, Suit::Diamonds, Suit::Spades];
+                    hands.push(
+                        suited
+                            .iter()
+                            .map(|rank| Card {
+                                rank: *rank,
+                                suit: Suit::Hearts,
+                            })
+                            .chain(rest.iter().zip(other_suits.iter().cycle()).map(
+                                |(rank, suit)| Card {
+                                    rank: *rank,
+                                    suit: *suit,
+                                },
+                            ))
+                            .collect(),
+                    );
+                }
+            }
+        }
+        hands
+    }
+
+    #[test]
+    fn has_same_order_as_short_deck_reference_on_every_hand_class() {
+        let hands = get_short_deck_hand_classes();
+        assert!(hands.iter().all(|cards| cards.iter().unique().count() == 7));
+        for rules in [
+            Rules::short_deck(Ante::ButtonBlind),
+            Rules::short_deck(Ante::AnteOnly),
+        ] {
+            assert_same_order_as_short_deck_reference(&rules, hands.iter().cloned());
+        }
+    }
+}
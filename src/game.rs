@@ -1,18 +1,27 @@
 use std::collections::HashMap;
 
+use itertools::Itertools;
+
 use crate::{
-    card::{Card, Rank},
-    player::Player,
+    card::{Card, Rank, Suit},
+    evaluator::{card_to_mask, cards_to_mask, evaluate, get_mask_combinations, CardMask},
+    rules::Rules,
     strength::{
-        flush::get_flush, full_house::get_full_house, high_cards::get_high_cards,
-        one_pair::get_one_pair, quads::get_quads, straight::get_straight,
-        straight_flush::get_straight_flush, three_of_a_kind::get_three_of_a_kind,
+        flush::get_flush,
+        full_house::get_full_house,
+        high_cards::get_high_cards,
+        low::{calc_razz_points, get_low_points, get_mask_low_rank_counts, get_razz_low_counts},
+        one_pair::get_one_pair,
+        quads::get_quads,
+        straight::get_straight,
+        straight_flush::get_straight_flush,
+        three_of_a_kind::get_three_of_a_kind,
         two_pairs::get_two_pairs,
     },
     GameType,
 };
 
-fn sort_by_rank_desc(cards: &mut Vec<Card>) {
+fn sort_by_rank_desc(cards: &mut [Card]) {
     cards.sort_by(|a, b| b.rank.partial_cmp(&a.rank).unwrap());
 }
 
@@ -33,20 +42,149 @@ pub enum HandCombination {
 pub struct HandStrength {
     pub hand_combination: HandCombination,
     pub points: u64,
+    /// Points of the qualifying low hand in split pot games.
+    pub low: Option<u64>,
 }
 
+/// High hand, and low hand in split pot games, of every player holding the cards
+/// of one of `hole_masks` with the cards of `board_mask` on the board.
 pub fn get_results(
     game_type: GameType,
-    trips_beat_straight: bool,
-    players: &[Player],
-    board: &[Card],
+    rules: &Rules,
+    hole_masks: impl IntoIterator<Item = CardMask>,
+    board_mask: CardMask,
 ) -> Vec<HandStrength> {
-    players
+    // Every three cards of a board of five cards at most
+    let mut board_masks = [0; 10];
+    let board_mask_count = if game_type.is_omaha() {
+        board_masks
+            .iter_mut()
+            .zip(get_mask_combinations(board_mask, 3))
+            .map(|(slot, mask)| *slot = mask)
+            .count()
+    } else {
+        0
+    };
+    let board_masks = &board_masks[..board_mask_count];
+
+    hole_masks
+        .into_iter()
+        .map(|hole| match game_type {
+            GameType::Omaha | GameType::Omaha5 | GameType::Omaha6 => {
+                get_omaha_hand_strength(board_masks, hole, rules)
+            }
+            GameType::OmahaHiLo => {
+                let mut hand_strength = get_omaha_hand_strength(board_masks, hole, rules);
+                hand_strength.low = get_omaha_low(board_masks, hole);
+                hand_strength
+            }
+            GameType::SevenCardStud => evaluate(hole, rules),
+            GameType::SevenCardStudHiLo => {
+                let mut hand_strength = evaluate(hole, rules);
+                hand_strength.low = get_low_points(hole);
+                hand_strength
+            }
+            GameType::Razz => get_razz_hand_strength(&get_mask_low_rank_counts(hole)),
+            GameType::TexasHoldem | GameType::ShortdeckHoldem => evaluate(board_mask | hole, rules),
+        })
+        .collect()
+}
+
+/// Masks of every `count` card combination of `cards`.
+pub(crate) fn get_combination_masks(cards: &[Card], count: usize) -> Vec<CardMask> {
+    cards
         .iter()
-        .map(|p| get_hand_strength(game_type, board, &p.hand, trips_beat_straight))
+        .map(card_to_mask)
+        .combinations(count)
+        .map(|masks| masks.into_iter().fold(0, |mask, m| mask | m))
         .collect()
 }
 
+/// Scores every legal Omaha hand (two of the hole cards and three board cards) with
+/// the Texas Hold'em evaluator and keeps the best one.
+pub(crate) fn get_omaha_hand_strength(
+    board_masks: &[CardMask],
+    hole: CardMask,
+    rules: &Rules,
+) -> HandStrength {
+    get_mask_combinations(hole, 2)
+        .cartesian_product(board_masks.iter())
+        .map(|(player_mask, board_mask)| evaluate(player_mask | board_mask, rules))
+        .max_by_key(|hs| hs.points)
+        .unwrap()
+}
+
+/// High hand of `hole` on a board given as its mask and its three card masks,
+/// Omaha hands using two hole cards and three board cards.
+pub(crate) fn get_board_hand_strength(
+    game_type: GameType,
+    rules: &Rules,
+    board_mask: CardMask,
+    board_masks: &[CardMask],
+    hole: CardMask,
+) -> HandStrength {
+    if game_type.is_omaha() {
+        get_omaha_hand_strength(board_masks, hole, rules)
+    } else {
+        evaluate(board_mask | hole, rules)
+    }
+}
+
+/// High hand of every two of `cards` held on `board`, with the positions of both
+/// cards in `cards`.
+pub(crate) fn get_two_card_hand_strengths(
+    game_type: GameType,
+    rules: &Rules,
+    cards: &[Card],
+    board: &[Card],
+) -> Vec<((usize, usize), HandStrength)> {
+    let board_mask = cards_to_mask(board);
+    let board_masks = get_combination_masks(board, 3);
+    (0..cards.len())
+        .tuple_combinations()
+        .map(|(i, j)| {
+            let hand_strength = get_board_hand_strength(
+                game_type,
+                rules,
+                board_mask,
+                &board_masks,
+                card_to_mask(&cards[i]) | card_to_mask(&cards[j]),
+            );
+            ((i, j), hand_strength)
+        })
+        .collect()
+}
+
+/// Best eight or better low made from two of the hole cards and three board cards.
+fn get_omaha_low(board_masks: &[CardMask], hole: CardMask) -> Option<u64> {
+    get_mask_combinations(hole, 2)
+        .cartesian_product(board_masks.iter())
+        .filter_map(|(hole_mask, board_mask)| get_low_points(hole_mask | board_mask))
+        .max()
+}
+
+/// In Razz only the low hand counts, so its points are the Razz points and the
+/// combination describes the pairs that hurt the low. Cards are given by their
+/// `get_low_rank_counts`.
+pub(crate) fn get_razz_hand_strength(counts: &[usize; 14]) -> HandStrength {
+    let hand = get_razz_low_counts(counts);
+    let max_same_rank = hand.iter().max().cloned().unwrap_or(0);
+    let distinct_ranks = hand.iter().filter(|count| **count > 0).count();
+
+    HandStrength {
+        hand_combination: match (distinct_ranks, max_same_rank) {
+            (_, 4) => HandCombination::Quads,
+            (2, 3) => HandCombination::FullHouse,
+            (_, 3) => HandCombination::TreeOfAKind,
+            (3, 2) => HandCombination::TwoPairs,
+            (_, 2) => HandCombination::OnePair,
+            _ => HandCombination::HighCards,
+        },
+        points: calc_razz_points(&hand),
+        low: None,
+    }
+}
+
 fn calc_points(start_points: u64, cards: &[Card]) -> u64 {
     let mut points = start_points;
     cards.iter().for_each(|c| {
@@ -56,15 +194,8 @@ fn calc_points(start_points: u64, cards: &[Card]) -> u64 {
     points
 }
 
-fn get_hand_strength(
-    game_type: GameType,
-    board: &[Card],
-    player_cards: &[Card],
-    trips_beat_straight: bool,
-) -> HandStrength {
-    let mut cards: Vec<Card> = board.iter().chain(player_cards).cloned().collect();
-    sort_by_rank_desc(&mut cards);
-
+/// Cards by suit and by rank, every group from the highest rank down.
+pub(crate) fn group_cards(cards: &[Card]) -> (HashMap<Suit, Vec<Card>>, HashMap<Rank, Vec<Card>>) {
     let mut suit_map = HashMap::with_capacity(4);
     let mut rank_map = HashMap::with_capacity(12);
 
@@ -80,115 +211,350 @@ fn get_hand_strength(
         entry.push(*c);
         sort_by_rank_desc(entry);
     }
+    (suit_map, rank_map)
+}
 
-    if let Some(hand) = get_straight_flush(game_type, &suit_map, &player_cards) {
-        if hand[0].rank == Rank::Ace {
-            return HandStrength {
-                hand_combination: HandCombination::RoyalFlush,
-                points: calc_points(10, &hand),
-            };
-        }
-        return HandStrength {
-            hand_combination: HandCombination::StraightFlush,
-            points: calc_points(9, &hand),
-        };
-    }
-
-    if let Some(hand) = get_quads(game_type, &cards, &rank_map, &player_cards, &board) {
-        return HandStrength {
-            hand_combination: HandCombination::Quads,
-            points: calc_points(8, &hand),
-        };
-    }
+/// Best hand of a player with the cards making it, found by the per-combination
+/// functions on `Card`s. Much slower than `evaluator`, it is used where the cards
+/// matter. Omaha hands with more than two hole cards may use too many of them,
+/// pass one two plus three combination at a time instead.
+pub fn get_hand_strength(
+    game_type: GameType,
+    board: &[Card],
+    player_cards: &[Card],
+    rules: &Rules,
+) -> (HandStrength, Vec<Card>) {
+    let mut cards: Vec<Card> = board.iter().chain(player_cards).cloned().collect();
+    sort_by_rank_desc(&mut cards);
+    let (suit_map, rank_map) = group_cards(&cards);
 
-    if game_type != GameType::ShortdeckHoldem {
-        if let Some(hand) = get_full_house(game_type, &rank_map, &player_cards) {
-            return HandStrength {
-                hand_combination: HandCombination::FullHouse,
-                points: calc_points(7, &hand),
-            };
-        }
-        if let Some(hand) = get_flush(game_type, &suit_map, &player_cards, &board) {
-            return HandStrength {
-                hand_combination: HandCombination::Flush,
-                points: calc_points(6, &hand),
-            };
-        }
-        if let Some(hand) = get_straight(game_type, &cards, &rank_map) {
-            return HandStrength {
-                hand_combination: HandCombination::Straight,
-                points: calc_points(5, &hand),
-            };
-        }
-        if let Some(hand) = get_three_of_a_kind(game_type, &cards, &rank_map, &player_cards, &board)
-        {
-            return HandStrength {
-                hand_combination: HandCombination::TreeOfAKind,
-                points: calc_points(4, &hand),
-            };
-        }
-    } else {
-        if let Some(hand) = get_flush(game_type, &suit_map, &player_cards, &board) {
-            return HandStrength {
-                hand_combination: HandCombination::Flush,
-                points: calc_points(7, &hand),
-            };
-        }
-        if let Some(hand) = get_full_house(game_type, &rank_map, &player_cards) {
-            return HandStrength {
-                hand_combination: HandCombination::FullHouse,
-                points: calc_points(6, &hand),
-            };
-        }
-        if trips_beat_straight {
-            if let Some(hand) =
-                get_three_of_a_kind(game_type, &cards, &rank_map, &player_cards, &board)
-            {
-                return HandStrength {
-                    hand_combination: HandCombination::TreeOfAKind,
-                    points: calc_points(5, &hand),
-                };
+    for (i, hand_combination) in rules.ranking.iter().enumerate() {
+        let hand = match hand_combination {
+            HandCombination::RoyalFlush => get_straight_flush(game_type, &suit_map, player_cards)
+                .filter(|hand| {
+                    hand.iter().any(|c| c.rank == Rank::Ace)
+                        && hand.iter().any(|c| c.rank == Rank::King)
+                }),
+            HandCombination::StraightFlush => {
+                get_straight_flush(game_type, &suit_map, player_cards)
+            }
+            HandCombination::Quads => get_quads(game_type, &cards, &rank_map, player_cards, board),
+            HandCombination::FullHouse => get_full_house(game_type, &rank_map, player_cards),
+            HandCombination::Flush => get_flush(game_type, &suit_map, player_cards, board),
+            HandCombination::Straight => get_straight(game_type, &cards, &rank_map, player_cards),
+            HandCombination::TreeOfAKind => {
+                get_three_of_a_kind(game_type, &cards, &rank_map, player_cards, board)
             }
-            if let Some(hand) = get_straight(game_type, &cards, &rank_map) {
-                return HandStrength {
-                    hand_combination: HandCombination::Straight,
-                    points: calc_points(4, &hand),
-                };
+            HandCombination::TwoPairs => {
+                get_two_pairs(game_type, &cards, &rank_map, player_cards, board)
             }
-        } else {
-            if let Some(hand) = get_straight(game_type, &cards, &rank_map) {
-                return HandStrength {
-                    hand_combination: HandCombination::Straight,
-                    points: calc_points(5, &hand),
-                };
+            HandCombination::OnePair => {
+                get_one_pair(game_type, &cards, &rank_map, player_cards, board)
             }
-            if let Some(hand) =
-                get_three_of_a_kind(game_type, &cards, &rank_map, &player_cards, &board)
-            {
-                return HandStrength {
-                    hand_combination: HandCombination::TreeOfAKind,
-                    points: calc_points(4, &hand),
-                };
+            HandCombination::HighCards => {
+                Some(get_high_cards(game_type, &cards, player_cards, board))
             }
+        };
+
+        if let Some(hand) = hand {
+            let hand_strength = HandStrength {
+                hand_combination: *hand_combination,
+                points: calc_points((rules.ranking.len() - i) as u64, &hand),
+                low: None,
+            };
+            return (hand_strength, hand);
         }
     }
 
-    if let Some(hand) = get_two_pairs(game_type, &cards, &rank_map, &player_cards, &board) {
-        return HandStrength {
-            hand_combination: HandCombination::TwoPairs,
-            points: calc_points(3, &hand),
-        };
+    unreachable!("high cards always match")
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    use super::*;
+    use crate::{hand::sort_high_cards, rules::Ante, strength::short_deck::get_short_deck_hand};
+
+    #[test]
+    fn can_get_omaha_flush_instead_of_illegal_full_house() {
+        let board = Card::from_cards_str("9hJhKhKcQs").unwrap();
+        let player_cards = Card::from_cards_str("6h6sKd8h").unwrap();
+        let rules = Rules::new(GameType::Omaha);
+
+        assert_eq!(
+            get_hand_strength(GameType::Omaha, &board, &player_cards, &rules)
+                .0
+                .hand_combination,
+            HandCombination::FullHouse
+        );
+        assert_eq!(
+            get_omaha_hand_strength(
+                &get_combination_masks(&board, 3),
+                cards_to_mask(&player_cards),
+                &rules
+            )
+            .hand_combination,
+            HandCombination::Flush
+        );
     }
 
-    if let Some(hand) = get_one_pair(game_type, &cards, &rank_map, &player_cards, &board) {
-        return HandStrength {
-            hand_combination: HandCombination::OnePair,
-            points: calc_points(2, &hand),
-        };
+    #[test]
+    fn can_get_omaha_two_pairs_from_lower_player_cards() {
+        assert_eq!(
+            get_omaha_hand_strength(
+                &get_combination_masks(&Card::from_cards_str("2hTc8d3h6d").unwrap(), 3),
+                cards_to_mask(&Card::from_cards_str("QsQh8hTh").unwrap()),
+                &Rules::new(GameType::Omaha)
+            )
+            .hand_combination,
+            HandCombination::TwoPairs
+        );
     }
 
-    HandStrength {
-        hand_combination: HandCombination::HighCards,
-        points: calc_points(1, &get_high_cards(game_type, &cards, &player_cards, &board)),
+    #[test]
+    fn can_get_omaha_one_pair_from_four_of_a_kind_in_hand() {
+        assert_eq!(
+            get_omaha_hand_strength(
+                &get_combination_masks(&Card::from_cards_str("2c3d4h5s9c").unwrap(), 3),
+                cards_to_mask(&Card::from_cards_str("AhAsAdAc").unwrap()),
+                &Rules::new(GameType::Omaha)
+            )
+            .hand_combination,
+            HandCombination::OnePair
+        );
+    }
+
+    #[test]
+    fn cannot_get_omaha_flush_with_one_suited_player_card() {
+        assert_eq!(
+            get_omaha_hand_strength(
+                &get_combination_masks(&Card::from_cards_str("2h5h7h9hJc").unwrap(), 3),
+                cards_to_mask(&Card::from_cards_str("AhKdKsKc").unwrap()),
+                &Rules::new(GameType::Omaha)
+            )
+            .hand_combination,
+            HandCombination::OnePair
+        );
+    }
+
+    #[test]
+    fn can_get_omaha_straight() {
+        let strength = get_omaha_hand_strength(
+            &get_combination_masks(&Card::from_cards_str("7s6d5h8c9d").unwrap(), 3),
+            cards_to_mask(&Card::from_cards_str("4h3cKdKc").unwrap()),
+            &Rules::new(GameType::Omaha),
+        );
+        assert_eq!(strength.hand_combination, HandCombination::Straight);
+        assert_eq!(
+            strength.points,
+            evaluate(
+                cards_to_mask(&Card::from_cards_str("7s6d5h4h3c").unwrap()),
+                &Rules::new(GameType::Omaha)
+            )
+            .points
+        );
+    }
+
+    #[test]
+    fn can_get_omaha5_hand_strength() {
+        assert_eq!(
+            get_omaha_hand_strength(
+                &get_combination_masks(&Card::from_cards_str("QhJh9h4s5c").unwrap(), 3),
+                cards_to_mask(&Card::from_cards_str("AhKh2c3d9s").unwrap()),
+                &Rules::new(GameType::Omaha)
+            )
+            .hand_combination,
+            HandCombination::Flush
+        );
+        assert_eq!(
+            get_omaha_hand_strength(
+                &get_combination_masks(&Card::from_cards_str("QhJhTh4s5c").unwrap(), 3),
+                cards_to_mask(&Card::from_cards_str("AhKd2c3d9s").unwrap()),
+                &Rules::new(GameType::Omaha)
+            )
+            .hand_combination,
+            HandCombination::Straight
+        );
+    }
+
+    #[test]
+    fn can_get_omaha6_hand_strength() {
+        assert_eq!(
+            get_omaha_hand_strength(
+                &get_combination_masks(&Card::from_cards_str("QhJh9h4s5c").unwrap(), 3),
+                cards_to_mask(&Card::from_cards_str("AhKd2c3d9s9d").unwrap()),
+                &Rules::new(GameType::Omaha)
+            )
+            .hand_combination,
+            HandCombination::TreeOfAKind
+        );
+        assert_eq!(
+            get_omaha_hand_strength(
+                &get_combination_masks(&Card::from_cards_str("KhKcQh4s5c").unwrap(), 3),
+                cards_to_mask(&Card::from_cards_str("AhAdAcAs9s8d").unwrap()),
+                &Rules::new(GameType::Omaha)
+            )
+            .hand_combination,
+            HandCombination::TwoPairs
+        );
+    }
+
+    #[test]
+    fn can_get_omaha_low() {
+        assert_eq!(
+            get_omaha_low(
+                &get_combination_masks(&Card::from_cards_str("3s5d8hJcQd").unwrap(), 3),
+                cards_to_mask(&Card::from_cards_str("Ah2cKdKc").unwrap())
+            ),
+            get_low_points(cards_to_mask(&Card::from_cards_str("8h5d3s2cAh").unwrap()))
+        );
+    }
+
+    #[test]
+    fn cannot_get_omaha_low_with_one_low_player_card() {
+        assert_eq!(
+            get_omaha_low(
+                &get_combination_masks(&Card::from_cards_str("2s3d4h5cJd").unwrap(), 3),
+                cards_to_mask(&Card::from_cards_str("AhKcKdQc").unwrap())
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn cannot_get_omaha_low_with_two_low_board_cards() {
+        assert_eq!(
+            get_omaha_low(
+                &get_combination_masks(&Card::from_cards_str("5s9dTh5cJd").unwrap(), 3),
+                cards_to_mask(&Card::from_cards_str("Ah2c3d4c").unwrap())
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn can_get_omaha_low_ignoring_counterfeit_player_card() {
+        assert_eq!(
+            get_omaha_low(
+                &get_combination_masks(&Card::from_cards_str("2s4d7h9cJd").unwrap(), 3),
+                cards_to_mask(&Card::from_cards_str("Ah2c3dKc").unwrap())
+            ),
+            get_low_points(cards_to_mask(&Card::from_cards_str("7h4d3d2sAh").unwrap()))
+        );
+    }
+
+    #[test]
+    fn can_get_stud_hi_lo_results() {
+        let players = [
+            cards_to_mask(&Card::from_cards_str("Ah2c3d4s8hKdKs").unwrap()),
+            cards_to_mask(&Card::from_cards_str("QhQdQc9s9d5c6c").unwrap()),
+        ];
+        let results = get_results(
+            GameType::SevenCardStudHiLo,
+            &Rules::new(GameType::SevenCardStudHiLo),
+            players,
+            0,
+        );
+
+        assert_eq!(results[0].hand_combination, HandCombination::OnePair);
+        assert_eq!(
+            results[0].low,
+            get_low_points(cards_to_mask(&Card::from_cards_str("8h4s3d2cAh").unwrap()))
+        );
+        assert_eq!(results[1].hand_combination, HandCombination::FullHouse);
+        assert_eq!(results[1].low, None);
+    }
+
+    #[test]
+    fn can_get_razz_results() {
+        let players = [
+            cards_to_mask(&Card::from_cards_str("KhKdKc5s4d3c2c").unwrap()),
+            cards_to_mask(&Card::from_cards_str("AhAd2h2d3h3d4c").unwrap()),
+        ];
+        let results = get_results(GameType::Razz, &Rules::new(GameType::Razz), players, 0);
+
+        assert_eq!(results[0].hand_combination, HandCombination::HighCards);
+        assert_eq!(results[1].hand_combination, HandCombination::OnePair);
+        assert!(results[0].points > results[1].points);
+    }
+
+    #[test]
+    fn short_deck_matches_reference() {
+        for rules in [
+            Rules::short_deck(Ante::ButtonBlind),
+            Rules::short_deck(Ante::AnteOnly),
+        ] {
+            let mut rng = StdRng::seed_from_u64(6);
+            let mut deck = rules.get_deck();
+            for _ in 0..20000 {
+                deck.shuffle(&mut rng);
+                let cards = &deck[..7];
+                let (strength, _) =
+                    get_hand_strength(GameType::ShortdeckHoldem, &cards[2..], &cards[..2], &rules);
+
+                assert_eq!(
+                    strength.hand_combination,
+                    get_short_deck_hand(cards, &rules).0,
+                    "{:?}",
+                    cards
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn omaha_matches_per_combination_functions() {
+        let rules = Rules::new(GameType::Omaha);
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut deck = rules.get_deck();
+        for i in 0..1500 {
+            deck.shuffle(&mut rng);
+            let board = &deck[..5];
+            // Omaha, Omaha5 and Omaha6 hands in turn
+            let player_cards = &deck[5..9 + i % 3];
+            // Best two plus three combination by the hand combination and the ranks
+            // of the cards the per-combination functions pick, scoring each one as a
+            // five card Hold'em hand
+            let ((expected_position, _), expected_cards) = player_cards
+                .iter()
+                .cloned()
+                .combinations(2)
+                .cartesian_product(board.iter().cloned().combinations(3))
+                .map(|(hole, board)| {
+                    let (expected, mut expected_cards) =
+                        get_hand_strength(GameType::TexasHoldem, &board, &hole, &rules);
+                    sort_high_cards(&mut expected_cards, expected.hand_combination);
+                    let position = rules
+                        .ranking
+                        .iter()
+                        .rev()
+                        .position(|hc| *hc == expected.hand_combination)
+                        .unwrap();
+                    let ranks: Vec<Rank> = expected_cards.iter().map(|c| c.rank).collect();
+                    ((position, ranks), [hole, board].concat())
+                })
+                .max_by(|(key, _), (other_key, _)| key.partial_cmp(other_key).unwrap())
+                .unwrap();
+            let expected_points = evaluate(cards_to_mask(&expected_cards), &rules).points;
+            let strength = get_omaha_hand_strength(
+                &get_combination_masks(board, 3),
+                cards_to_mask(player_cards),
+                &rules,
+            );
+
+            assert_eq!(
+                strength.hand_combination,
+                rules.ranking[rules.ranking.len() - 1 - expected_position],
+                "{:?} {:?}",
+                player_cards,
+                board
+            );
+            assert_eq!(
+                strength.points, expected_points,
+                "{:?} {:?}",
+                player_cards, board
+            );
+        }
     }
 }
@@ -0,0 +1,501 @@
+use std::cmp::Ordering;
+
+use itertools::Itertools;
+
+use crate::{
+    card::{Card, Rank},
+    evaluator::{cards_to_mask, evaluate as evaluate_mask},
+    game::{get_hand_strength, get_razz_hand_strength, HandCombination, HandStrength},
+    rules::Rules,
+    strength::low::{get_low_rank_counts, get_razz_low},
+    Error, GameType,
+};
+
+/// Best five card hand of a player. Hands compare by `value` only, so two hands
+/// with the same value are equal even if their suits differ.
+#[derive(Debug, Clone)]
+pub struct HandRank {
+    pub game_type: GameType,
+    pub hand_combination: HandCombination,
+    /// The five chosen cards, most important first: bigger rank groups before
+    /// kickers and straights from their highest card down.
+    pub cards: Vec<Card>,
+    /// Higher is better, only comparable between hands of the same game.
+    pub value: u64,
+}
+
+impl PartialEq for HandRank {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for HandRank {}
+
+impl PartialOrd for HandRank {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HandRank {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+fn get_rank_name(rank: Rank) -> &'static str {
+    match rank {
+        Rank::Two => "Two",
+        Rank::Three => "Three",
+        Rank::Four => "Four",
+        Rank::Five => "Five",
+        Rank::Six => "Six",
+        Rank::Seven => "Seven",
+        Rank::Eight => "Eight",
+        Rank::Nine => "Nine",
+        Rank::Ten => "Ten",
+        Rank::Jack => "Jack",
+        Rank::Queen => "Queen",
+        Rank::King => "King",
+        Rank::Ace => "Ace",
+    }
+}
+
+fn get_plural_rank_name(rank: Rank) -> String {
+    match rank {
+        Rank::Six => "Sixes".to_string(),
+        _ => format!("{}s", get_rank_name(rank)),
+    }
+}
+
+/// "with a King kicker" or "with an Ace kicker".
+fn get_kicker_description(rank: Rank) -> String {
+    let article = match rank {
+        Rank::Ace | Rank::Eight => "an",
+        _ => "a",
+    };
+    format!("with {} {} kicker", article, get_rank_name(rank))
+}
+
+impl HandRank {
+    /// Describes the hand like "Full house, Kings full of Fives" or "Two pair,
+    /// Jacks and Fours with a Queen kicker". Razz hands without a pair are named
+    /// after their two highest cards, like "Seven-Six low".
+    pub fn description(&self) -> String {
+        let ranks: Vec<Rank> = self.cards.iter().map(|c| c.rank).collect();
+        let name = |i: usize| get_rank_name(ranks[i]);
+        let plural_name = |i: usize| get_plural_rank_name(ranks[i]);
+
+        match self.hand_combination {
+            HandCombination::HighCards if self.game_type == GameType::Razz => {
+                format!("{}-{} low", name(0), name(1))
+            }
+            HandCombination::RoyalFlush => "Royal flush".to_string(),
+            HandCombination::StraightFlush => format!("Straight flush, {} high", name(0)),
+            HandCombination::Quads => format!(
+                "Four of a kind, {} {}",
+                plural_name(0),
+                get_kicker_description(ranks[4])
+            ),
+            HandCombination::FullHouse => {
+                format!("Full house, {} full of {}", plural_name(0), plural_name(3))
+            }
+            HandCombination::Flush => format!("Flush, {} high", name(0)),
+            HandCombination::Straight => format!("Straight, {} high", name(0)),
+            HandCombination::TreeOfAKind => format!(
+                "Three of a kind, {} {}",
+                plural_name(0),
+                get_kicker_description(ranks[3])
+            ),
+            HandCombination::TwoPairs => format!(
+                "Two pair, {} and {} {}",
+                plural_name(0),
+                plural_name(2),
+                get_kicker_description(ranks[4])
+            ),
+            HandCombination::OnePair => format!(
+                "One pair, {} {}",
+                plural_name(0),
+                get_kicker_description(ranks[2])
+            ),
+            HandCombination::HighCards => format!("High card, {}", name(0)),
+        }
+    }
+}
+
+/// Number of cards the best hand can be made of, Omaha hands use exactly two
+/// hole cards and three board cards.
+fn get_usable_card_count(game_type: GameType, hole: &[Card], board: &[Card]) -> usize {
+    if game_type.is_omaha() {
+        hole.len().min(2) + board.len().min(3)
+    } else {
+        hole.len() + board.len()
+    }
+}
+
+/// Orders the cards of a high hand by rank group size, then rank. Aces of wheel
+/// straights go last.
+pub(crate) fn sort_high_cards(cards: &mut [Card], hand_combination: HandCombination) {
+    let counts = cards.iter().map(|c| c.rank).counts();
+    cards.sort_by(|a, b| {
+        (counts[&b.rank], b.rank)
+            .partial_cmp(&(counts[&a.rank], a.rank))
+            .unwrap()
+    });
+
+    let is_straight = matches!(
+        hand_combination,
+        HandCombination::Straight | HandCombination::StraightFlush
+    );
+    if is_straight && cards[0].rank == Rank::Ace && cards[1].rank != Rank::King {
+        cards.rotate_left(1);
+    }
+}
+
+/// Checks the hole and board sizes of `game_type`, that every card is in the deck
+/// of `rules` and that no card is used twice.
+pub(crate) fn validate_cards(
+    game_type: GameType,
+    rules: &Rules,
+    hole: &[Card],
+    board: &[Card],
+) -> Result<(), Error> {
+    if board.len() > game_type.board_cards() {
+        return Err(Error::TooManyBoardCards {
+            game_type,
+            cards: board.len(),
+        });
+    }
+    if hole.len() > game_type.hole_cards() {
+        return Err(Error::WrongHandSize {
+            game_type,
+            player: 0,
+            cards: hole.len(),
+        });
+    }
+    if let Some(card) = hole
+        .iter()
+        .chain(board)
+        .find(|c| c.rank < rules.lowest_rank)
+    {
+        return Err(Error::CardNotInDeck(*card));
+    }
+    if let Some(card) = hole.iter().chain(board).duplicates().next() {
+        return Err(Error::DuplicateCard(*card));
+    }
+    Ok(())
+}
+
+/// Finds the best five card hand of a player. Stud players pass all their cards as
+/// `hole` and no board. Hi-lo games rank the high hand, Razz the low hand.
+pub fn evaluate(game_type: GameType, hole: &[Card], board: &[Card]) -> Result<HandRank, Error> {
+    evaluate_with_rules(game_type, &Rules::new(game_type), hole, board)
+}
+
+/// Like `evaluate`, ranking high hands by `rules` instead of the standard rules
+/// of the game.
+pub fn evaluate_with_rules(
+    game_type: GameType,
+    rules: &Rules,
+    hole: &[Card],
+    board: &[Card],
+) -> Result<HandRank, Error> {
+    rules.validate()?;
+    validate_cards(game_type, rules, hole, board)?;
+    let usable_card_count = get_usable_card_count(game_type, hole, board);
+    if usable_card_count < 5 {
+        return Err(Error::NotEnoughCards(usable_card_count));
+    }
+
+    if game_type == GameType::Razz {
+        let cards = get_razz_low(hole);
+        let HandStrength {
+            hand_combination,
+            points,
+            ..
+        } = get_razz_hand_strength(&get_low_rank_counts(&cards));
+        return Ok(HandRank {
+            game_type,
+            hand_combination,
+            cards,
+            value: points,
+        });
+    }
+
+    let (mut cards, hand_strength) = if game_type.is_omaha() {
+        hole.iter()
+            .cloned()
+            .combinations(2)
+            .cartesian_product(board.iter().cloned().combinations(3).collect::<Vec<_>>())
+            .map(|(hole, board)| {
+                let cards: Vec<Card> = hole.into_iter().chain(board).collect();
+                let hand_strength = evaluate_mask(cards_to_mask(&cards), rules);
+                (cards, hand_strength)
+            })
+            .max_by_key(|(_, hs)| hs.points)
+            .unwrap()
+    } else {
+        // Values stay on the evaluator scale so hands of every game compare alike.
+        let (_, chosen) = get_hand_strength(game_type, board, hole, rules);
+        let cards: Vec<Card> = hole
+            .iter()
+            .chain(board)
+            .filter(|c| chosen.contains(c))
+            .cloned()
+            .collect();
+        let hand_strength = evaluate_mask(cards_to_mask(&cards), rules);
+        (cards, hand_strength)
+    };
+    sort_high_cards(&mut cards, hand_strength.hand_combination);
+
+    Ok(HandRank {
+        game_type,
+        hand_combination: hand_strength.hand_combination,
+        cards,
+        value: hand_strength.points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Ante;
+
+    #[test]
+    fn can_evaluate_holdem_hand() {
+        let hand_rank = evaluate(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("KsKd").unwrap(),
+            &Card::from_cards_str("Kh5c5d9s2c").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(hand_rank.hand_combination, HandCombination::FullHouse);
+        assert_eq!(hand_rank.cards, Card::from_cards_str("KsKdKh5c5d").unwrap());
+    }
+
+    #[test]
+    fn can_evaluate_wheel() {
+        let hand_rank = evaluate(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("Ah2d").unwrap(),
+            &Card::from_cards_str("3c4s5hKdKc").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(hand_rank.hand_combination, HandCombination::Straight);
+        assert_eq!(hand_rank.cards, Card::from_cards_str("5h4s3c2dAh").unwrap());
+    }
+
+    #[test]
+    fn can_evaluate_with_rules() {
+        let trips = Card::from_cards_str("AhQd").unwrap();
+        let board = Card::from_cards_str("QsQc6d7c8s").unwrap();
+        let evaluate_short_deck = |rules: &Rules, hole: &[Card]| {
+            evaluate_with_rules(GameType::ShortdeckHoldem, rules, hole, &board).unwrap()
+        };
+        let straight = Card::from_cards_str("9hAd").unwrap();
+        let button_blind = Rules::short_deck(Ante::ButtonBlind);
+        let ante_only = Rules::short_deck(Ante::AnteOnly);
+
+        assert_eq!(
+            evaluate_short_deck(&ante_only, &trips).hand_combination,
+            HandCombination::TreeOfAKind
+        );
+        assert_eq!(
+            evaluate_short_deck(&ante_only, &straight).hand_combination,
+            HandCombination::Straight
+        );
+        assert!(
+            evaluate_short_deck(&button_blind, &straight)
+                > evaluate_short_deck(&button_blind, &trips)
+        );
+        assert!(
+            evaluate_short_deck(&ante_only, &straight) < evaluate_short_deck(&ante_only, &trips)
+        );
+    }
+
+    #[test]
+    fn can_evaluate_omaha_hand() {
+        // Four hearts on the board are no flush with a single heart in hand
+        let hand_rank = evaluate(
+            GameType::Omaha,
+            &Card::from_cards_str("AhKsQd2c").unwrap(),
+            &Card::from_cards_str("2h5h8h9hJc").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(hand_rank.hand_combination, HandCombination::OnePair);
+        assert_eq!(hand_rank.cards, Card::from_cards_str("2c2hAhJc9h").unwrap());
+    }
+
+    #[test]
+    fn can_evaluate_stud_and_razz_hands() {
+        let stud = evaluate(
+            GameType::SevenCardStud,
+            &Card::from_cards_str("AhAdKc7s6s5s2c").unwrap(),
+            &[],
+        )
+        .unwrap();
+        let razz = evaluate(
+            GameType::Razz,
+            &Card::from_cards_str("AhAdKc7s6s5s2c").unwrap(),
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(stud.hand_combination, HandCombination::OnePair);
+        assert_eq!(stud.cards, Card::from_cards_str("AhAdKc7s6s").unwrap());
+        assert_eq!(razz.hand_combination, HandCombination::HighCards);
+        assert_eq!(razz.cards, Card::from_cards_str("7s6s5s2cAh").unwrap());
+    }
+
+    #[test]
+    fn can_sort_hand_ranks() {
+        let board = Card::from_cards_str("Ks9d5c2h3s").unwrap();
+        let mut hand_ranks = [
+            evaluate(
+                GameType::TexasHoldem,
+                &Card::from_cards_str("AhAd").unwrap(),
+                &board,
+            )
+            .unwrap(),
+            evaluate(
+                GameType::TexasHoldem,
+                &Card::from_cards_str("KhKd").unwrap(),
+                &board,
+            )
+            .unwrap(),
+            evaluate(
+                GameType::TexasHoldem,
+                &Card::from_cards_str("AcQd").unwrap(),
+                &board,
+            )
+            .unwrap(),
+            evaluate(
+                GameType::TexasHoldem,
+                &Card::from_cards_str("Ac4d").unwrap(),
+                &board,
+            )
+            .unwrap(),
+        ];
+        hand_ranks.sort();
+
+        assert_eq!(
+            hand_ranks
+                .iter()
+                .map(|hr| hr.hand_combination)
+                .collect::<Vec<HandCombination>>(),
+            vec![
+                HandCombination::HighCards,
+                HandCombination::OnePair,
+                HandCombination::TreeOfAKind,
+                HandCombination::Straight,
+            ]
+        );
+        assert_eq!(
+            evaluate(
+                GameType::TexasHoldem,
+                &Card::from_cards_str("AhQh").unwrap(),
+                &board
+            )
+            .unwrap(),
+            evaluate(
+                GameType::TexasHoldem,
+                &Card::from_cards_str("AcQd").unwrap(),
+                &board
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn cannot_evaluate_invalid_hands() {
+        let cards = Card::from_cards_str("AhKh2c3d").unwrap();
+
+        assert_eq!(
+            evaluate(GameType::TexasHoldem, &cards, &[]).unwrap_err(),
+            Error::WrongHandSize {
+                game_type: GameType::TexasHoldem,
+                player: 0,
+                cards: 4
+            }
+        );
+        assert_eq!(
+            evaluate(GameType::TexasHoldem, &cards[..2], &cards[2..]).unwrap_err(),
+            Error::NotEnoughCards(4)
+        );
+        assert_eq!(
+            evaluate(GameType::Omaha, &cards, &cards[..3]).unwrap_err(),
+            Error::DuplicateCard(cards[0])
+        );
+        assert_eq!(
+            evaluate(GameType::ShortdeckHoldem, &cards[..2], &cards[2..]).unwrap_err(),
+            Error::CardNotInDeck(cards[2])
+        );
+    }
+
+    #[test]
+    fn can_describe_hands() {
+        for (hole, board, description) in [
+            ("AhKh", "QhJhTh2c3d", "Royal flush"),
+            ("5h4h", "3h2hAh9c9d", "Straight flush, Five high"),
+            (
+                "9c9d",
+                "9h9s2c3d4h",
+                "Four of a kind, Nines with a Four kicker",
+            ),
+            ("KsKd", "Kh5c5d9s2c", "Full house, Kings full of Fives"),
+            ("Ah4h", "9h6h2h5c5d", "Flush, Ace high"),
+            ("Ac2d", "3h4s5c9d9h", "Straight, Five high"),
+            (
+                "6c6d",
+                "6hAs8c3d4h",
+                "Three of a kind, Sixes with an Ace kicker",
+            ),
+            (
+                "JcJd",
+                "4h4sQc3d2h",
+                "Two pair, Jacks and Fours with a Queen kicker",
+            ),
+            ("AcAd", "KhQs9c3d2h", "One pair, Aces with a King kicker"),
+            ("9c8d", "AhQsKc3d4h", "High card, Ace"),
+        ] {
+            assert_eq!(
+                evaluate(
+                    GameType::TexasHoldem,
+                    &Card::from_cards_str(hole).unwrap(),
+                    &Card::from_cards_str(board).unwrap()
+                )
+                .unwrap()
+                .description(),
+                description
+            );
+        }
+    }
+
+    #[test]
+    fn can_describe_razz_hands() {
+        assert_eq!(
+            evaluate(
+                GameType::Razz,
+                &Card::from_cards_str("AhAdKc7s6s5s2c").unwrap(),
+                &Card::from_cards_str("").unwrap()
+            )
+            .unwrap()
+            .description(),
+            "Seven-Six low"
+        );
+        assert_eq!(
+            evaluate(
+                GameType::Razz,
+                &Card::from_cards_str("KhKdKcQsQh2s2c").unwrap(),
+                &Card::from_cards_str("").unwrap()
+            )
+            .unwrap()
+            .description(),
+            "Two pair, Queens and Twos with a King kicker"
+        );
+    }
+}
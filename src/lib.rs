@@ -1,32 +1,99 @@
+#[cfg(test)]
 #[macro_use]
 extern crate maplit;
 
 use std::str::FromStr;
 
 use card::Card;
+pub use draw::{get_draws, Draw, DrawType, Draws};
+pub use error::Error;
+pub use game::HandCombination;
+pub use hand::{evaluate, evaluate_with_rules, HandRank};
+pub use nuts::{get_nut_rank, get_nuts, NutRank, Nuts};
+pub use rules::{Ante, LowStraight, Rules};
+pub use texture::{get_board_texture, BoardTexture, Connectedness, Pairing, Suits};
 
 pub mod card;
+mod draw;
+mod error;
+mod evaluator;
 mod game;
-mod player;
+mod hand;
+mod nuts;
+pub mod range;
+mod rules;
 mod strength;
 pub mod table;
+mod texture;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum GameType {
     TexasHoldem,
     ShortdeckHoldem,
     Omaha,
+    Omaha5,
+    Omaha6,
+    OmahaHiLo,
+    SevenCardStud,
+    SevenCardStudHiLo,
+    Razz,
+}
+
+impl GameType {
+    pub fn is_omaha(&self) -> bool {
+        matches!(
+            self,
+            GameType::Omaha | GameType::Omaha5 | GameType::Omaha6 | GameType::OmahaHiLo
+        )
+    }
+
+    /// Hi-lo games split the pot between the best high and the best low hand.
+    pub fn is_hi_lo(&self) -> bool {
+        matches!(self, GameType::OmahaHiLo | GameType::SevenCardStudHiLo)
+    }
+
+    /// Stud games have no community cards, every player gets their own cards.
+    pub fn is_stud(&self) -> bool {
+        matches!(
+            self,
+            GameType::SevenCardStud | GameType::SevenCardStudHiLo | GameType::Razz
+        )
+    }
+
+    pub fn hole_cards(&self) -> usize {
+        match self {
+            GameType::TexasHoldem | GameType::ShortdeckHoldem => 2,
+            GameType::Omaha | GameType::OmahaHiLo => 4,
+            GameType::Omaha5 => 5,
+            GameType::Omaha6 => 6,
+            GameType::SevenCardStud | GameType::SevenCardStudHiLo | GameType::Razz => 7,
+        }
+    }
+
+    pub fn board_cards(&self) -> usize {
+        if self.is_stud() {
+            0
+        } else {
+            5
+        }
+    }
 }
 
 impl FromStr for GameType {
-    type Err = &'static str;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "shortdeck_holdem" => Ok(GameType::ShortdeckHoldem),
             "texas_holdem" => Ok(GameType::TexasHoldem),
             "omaha" => Ok(GameType::Omaha),
-            _ => Err("no match"),
+            "omaha5" => Ok(GameType::Omaha5),
+            "omaha6" => Ok(GameType::Omaha6),
+            "omaha_hi_lo" => Ok(GameType::OmahaHiLo),
+            "stud" => Ok(GameType::SevenCardStud),
+            "stud_hi_lo" => Ok(GameType::SevenCardStudHiLo),
+            "razz" => Ok(GameType::Razz),
+            _ => Err(Error::UnknownGameType(s.to_string())),
         }
     }
 }
@@ -37,7 +104,7 @@ pub struct Cards {
 }
 
 impl FromStr for Cards {
-    type Err = &'static str;
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let cards = Card::from_cards_str(s);
@@ -55,6 +122,20 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn can_parse_game_type() {
+        assert_eq!(GameType::from_str("omaha5"), Ok(GameType::Omaha5));
+        assert_eq!(GameType::from_str("omaha6"), Ok(GameType::Omaha6));
+        assert_eq!(GameType::from_str("omaha_hi_lo"), Ok(GameType::OmahaHiLo));
+        assert_eq!(GameType::from_str("stud"), Ok(GameType::SevenCardStud));
+        assert_eq!(
+            GameType::from_str("stud_hi_lo"),
+            Ok(GameType::SevenCardStudHiLo)
+        );
+        assert_eq!(GameType::from_str("razz"), Ok(GameType::Razz));
+        assert!(GameType::from_str("omaha7").is_err());
+    }
+
     #[test]
     fn can_parse_cards() {
         assert_eq!(
@@ -1,4 +1,10 @@
-use poker_odds_calc::{table::Table, Cards, GameType};
+use poker_odds_calc::{
+    card::Card,
+    evaluate_with_rules, get_nut_rank, get_nuts,
+    table::{Seat, Table},
+    Ante, Cards, Error, GameType, Rules,
+};
+use std::time::Duration;
 use structopt::StructOpt;
 
 /// A basic example
@@ -10,7 +16,7 @@ struct Opt {
         short,
         long,
         parse(try_from_str),
-        help = "Select between texas_holdem, shortdeck_holdem and omaha"
+        help = "Select between texas_holdem, shortdeck_holdem, omaha, omaha5, omaha6, omaha_hi_lo, stud, stud_hi_lo and razz"
     )]
     pub game: GameType,
 
@@ -26,9 +32,17 @@ struct Opt {
         short,
         long = "player",
         parse(try_from_str),
-        help = "Define player hand (ex. `AcKh`)"
+        help = "Define player hand (ex. `AcKh`, may be partially known like `Ac`, in stud games all known cards of the player) or range (ex. `TT+,AQs-ATs,KJo:0.5`)"
     )]
-    pub players: Vec<Cards>,
+    pub players: Vec<Seat>,
+
+    #[structopt(
+        default_value = "0",
+        short,
+        long,
+        help = "Number of additional players with unknown cards"
+    )]
+    pub random: usize,
 
     #[structopt(
         default_value = "100000",
@@ -45,26 +59,265 @@ struct Opt {
     )]
     pub dead: Option<Cards>,
 
-    #[structopt(short, long, help = "Option only available for -g shortdeck_holdem")]
+    #[structopt(
+        short,
+        long,
+        help = "Option only available for -g shortdeck_holdem, plays the ante only variant"
+    )]
     pub tripsbeatstraight: bool,
+
+    #[structopt(long, help = "Seed for reproducible random runouts")]
+    pub seed: Option<u64>,
+
+    #[structopt(
+        long,
+        help = "Sample until every equity 95% confidence interval is narrower than this (ex. `0.01`)"
+    )]
+    pub tolerance: Option<f64>,
+
+    #[structopt(
+        default_value = "10000",
+        long,
+        help = "Time budget in ms when sampling until --tolerance is reached"
+    )]
+    pub max_time: u64,
+
+    #[structopt(long, help = "Print the equity of every combo of range players")]
+    pub combos: bool,
+
+    #[structopt(
+        long,
+        help = "Print the equity distribution over every street to come, dealing at most this many of each"
+    )]
+    pub streets: Option<u64>,
+
+    #[structopt(
+        long,
+        help = "Print who wins with every river card, or the turn cards grouped by equity on the flop"
+    )]
+    pub outs: bool,
+
+    #[structopt(
+        long,
+        help = "Print the nuts on the board and how every player ranks among all holdings"
+    )]
+    pub nuts: bool,
+
+    #[structopt(
+        long,
+        help = "Print the hand strength and potentials of the first player against the holdings of the second, comparing at most this many of them"
+    )]
+    pub potential: Option<u64>,
+}
+
+fn exit_on_error<T>(result: Result<T, Error>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// English ordinal of `n`, like `2nd` or `11th`.
+fn ordinal(n: usize) -> String {
+    let suffix = match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", n, suffix)
 }
 
 fn main() {
     let opt: Opt = Opt::from_args();
     println!("{:#?}", opt);
-    let table = Table::new(
-        opt.players,
-        opt.board.unwrap_or(Cards { cards: vec![] }).cards,
-        opt.dead.unwrap_or(Cards { cards: vec![] }).cards,
+    let mut seats = opt.players;
+    seats.extend((0..opt.random).map(|_| Seat::Hand(vec![])));
+    let board = opt.board.unwrap_or(Cards { cards: vec![] }).cards;
+    let rules = if opt.tripsbeatstraight && opt.game == GameType::ShortdeckHoldem {
+        Rules::short_deck(Ante::AnteOnly)
+    } else {
+        Rules::new(opt.game)
+    };
+    let table = exit_on_error(
+        Table::new(
+            opt.game,
+            seats,
+            board.clone(),
+            opt.dead.unwrap_or(Cards { cards: vec![] }).cards,
+        )
+        .and_then(|table| table.with_rules(rules.clone())),
     );
-    let result = table.get_results(opt.game, opt.limit, opt.tripsbeatstraight);
+    // Checked before any result is printed, as the nuts need a flop and a board game
+    let nuts = if opt.nuts {
+        Some(exit_on_error(get_nuts(opt.game, &rules, &board)))
+    } else {
+        None
+    };
+    let result = match opt.tolerance {
+        Some(tolerance) => table.get_results_until_converged(
+            tolerance,
+            Duration::from_millis(opt.max_time),
+            opt.seed,
+        ),
+        None => table.get_results(opt.limit, opt.seed),
+    };
 
     println!("time {}ms", result.time_in_ms);
     for pr in &result.player_results {
         println!(
-            "win {}% tie {}%",
-            (pr.wins as f64 * 100f64) / result.iterations as f64,
-            (pr.ties as f64 * 100f64) / result.iterations as f64,
+            "win {}% tie {}% equity {}%",
+            (pr.wins * 100f64) / result.iterations as f64,
+            (pr.ties * 100f64) / result.iterations as f64,
+            pr.equity * 100f64,
+        );
+        // Made hand so far, for players holding all their cards
+        if pr.hand.len() == opt.game.hole_cards() {
+            if let Ok(hand_rank) = evaluate_with_rules(opt.game, &rules, &pr.hand, &board) {
+                println!("  {}", hand_rank.description());
+            }
+            if opt.nuts {
+                match get_nut_rank(opt.game, &rules, &pr.hand, &board) {
+                    Ok(nut_rank) => println!(
+                        "  {} nuts, {} of {} holdings are better (top {}%)",
+                        ordinal(nut_rank.rank),
+                        nut_rank.better,
+                        nut_rank.holdings,
+                        nut_rank.top_fraction() * 100f64,
+                    ),
+                    Err(e) => println!("  {}", e),
+                }
+            }
+        }
+        if opt.game.is_hi_lo() {
+            println!(
+                "  high {}% low {}% scoop {}% quartered {}%",
+                (pr.high_wins * 100f64) / result.iterations as f64,
+                (pr.low_wins * 100f64) / result.iterations as f64,
+                (pr.scoops * 100f64) / result.iterations as f64,
+                (pr.quartered * 100f64) / result.iterations as f64,
+            );
+        }
+        if result.approximate {
+            println!(
+                "  equity 95% confidence interval {}% - {}%",
+                pr.confidence_interval.0 * 100f64,
+                pr.confidence_interval.1 * 100f64,
+            );
+        }
+        if opt.combos {
+            for cr in &pr.combo_results {
+                println!(
+                    "  {} weight {} equity {}% ({} iterations)",
+                    cr.hand.iter().map(Card::to_string).collect::<String>(),
+                    cr.weight,
+                    cr.equity * 100f64,
+                    cr.iterations,
+                );
+            }
+        }
+    }
+
+    if let Some(street_limit) = opt.streets {
+        for breakdown in
+            exit_on_error(table.get_street_progression(street_limit, opt.limit, opt.seed))
+        {
+            let street = match breakdown.board_cards {
+                3 => "flop".to_string(),
+                4 => "turn".to_string(),
+                5 => "river".to_string(),
+                board_cards => format!("{} board cards", board_cards),
+            };
+            println!(
+                "{}: {} streets, {} blocked, in {}ms",
+                street,
+                breakdown.street_results.len(),
+                breakdown.blocked,
+                breakdown.time_in_ms
+            );
+            for player in 0..result.player_results.len() {
+                if let Some(average_equity) = breakdown.get_average_equity(player) {
+                    println!(
+                        "player {} average equity {}%",
+                        player + 1,
+                        average_equity * 100f64
+                    );
+                }
+                for (i, count) in breakdown
+                    .get_equity_histogram(player, 10)
+                    .iter()
+                    .enumerate()
+                {
+                    println!("  {}-{}%: {}", i * 10, (i + 1) * 10, count);
+                }
+            }
+        }
+    }
+
+    if opt.outs {
+        let player_names = |players: &[usize]| -> String {
+            players
+                .iter()
+                .map(|player| (player + 1).to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        if board.len() + 2 == opt.game.board_cards() {
+            for group in exit_on_error(table.get_outs_by_equity(opt.limit, opt.seed)) {
+                println!(
+                    "equity {}: {}",
+                    group
+                        .equities
+                        .iter()
+                        .map(|equity| format!("{}%", equity * 100f64))
+                        .collect::<Vec<String>>()
+                        .join(" "),
+                    group.cards.iter().map(Card::to_string).collect::<String>(),
+                );
+            }
+        } else {
+            for out in exit_on_error(table.get_outs()) {
+                println!(
+                    "{}: player {} {:?}",
+                    out.card,
+                    player_names(&out.winners),
+                    out.hand_combinations,
+                );
+            }
+        }
+    }
+
+    if let Some(nuts) = nuts {
+        println!(
+            "nuts: {} with {}",
+            nuts.hand_rank.description(),
+            nuts.hole_cards
+                .iter()
+                .map(|cards| cards.iter().map(Card::to_string).collect::<String>())
+                .collect::<Vec<String>>()
+                .join(" "),
+        );
+    }
+
+    if let Some(holding_limit) = opt.potential {
+        let potential = exit_on_error(table.get_hand_potential(holding_limit, opt.limit, opt.seed));
+        println!(
+            "hand strength {}% (ahead {}% tied {}% behind {}%) in {}ms",
+            potential.get_hand_strength() * 100f64,
+            potential.ahead * 100f64,
+            potential.tied * 100f64,
+            potential.behind * 100f64,
+            potential.time_in_ms,
+        );
+        println!(
+            "  positive potential {}% negative potential {}% effective hand strength {}%",
+            potential.positive_potential * 100f64,
+            potential.negative_potential * 100f64,
+            potential.get_effective_hand_strength() * 100f64,
         );
     }
 }
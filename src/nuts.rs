@@ -0,0 +1,252 @@
+use std::collections::HashSet;
+
+use itertools::Itertools;
+
+use crate::{
+    card::Card,
+    evaluator::cards_to_mask,
+    game::{get_board_hand_strength, get_combination_masks, get_two_card_hand_strengths},
+    hand::{evaluate_with_rules, validate_cards, HandRank},
+    rules::Rules,
+    Error, GameType,
+};
+
+#[derive(Debug, Clone)]
+pub struct Nuts {
+    pub hand_rank: HandRank,
+    /// Every two hole cards making the nuts. Omaha holdings make them with any
+    /// other cards next to these two.
+    pub hole_cards: Vec<Vec<Card>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct NutRank {
+    /// 1 for the nuts, 2 for the second nuts, counting hands of different value.
+    pub rank: usize,
+    /// Holdings beating the player.
+    pub better: usize,
+    pub ties: usize,
+    /// Holdings dealt from the cards neither on the board nor held by the player.
+    pub holdings: usize,
+}
+
+impl NutRank {
+    /// Share of holdings beating the player, 0.05 for a hand in the top 5%.
+    pub fn top_fraction(&self) -> f64 {
+        self.better as f64 / self.holdings as f64
+    }
+}
+
+/// Best hand any hole cards make on a flop, turn or river, ranked by `rules`.
+/// Hi-lo games only look at the high hand.
+pub fn get_nuts(game_type: GameType, rules: &Rules, board: &[Card]) -> Result<Nuts, Error> {
+    check_cards(game_type, rules, &[], board)?;
+    let cards = rules.get_unused_cards(&[], board);
+    let hand_strengths = get_two_card_hand_strengths(game_type, rules, &cards, board);
+
+    let nut_points = hand_strengths
+        .iter()
+        .map(|(_, hs)| hs.points)
+        .max()
+        .unwrap_or(0);
+    let hole_cards: Vec<Vec<Card>> = hand_strengths
+        .iter()
+        .filter(|(_, hs)| hs.points == nut_points)
+        .map(|((i, j), _)| vec![cards[*i], cards[*j]])
+        .collect();
+    Ok(Nuts {
+        hand_rank: evaluate_with_rules(game_type, rules, &hole_cards[0], board)?,
+        hole_cards,
+    })
+}
+
+/// Ranks the hand of a player among every holding the other cards can be dealt,
+/// with Omaha holdings using two of their cards and three board cards. Holdings
+/// are counted one at a time, Omaha holdings of five or six cards run into the
+/// millions.
+pub fn get_nut_rank(
+    game_type: GameType,
+    rules: &Rules,
+    hole: &[Card],
+    board: &[Card],
+) -> Result<NutRank, Error> {
+    check_cards(game_type, rules, hole, board)?;
+    if hole.len() != game_type.hole_cards() {
+        return Err(Error::UnknownCards);
+    }
+    let cards = rules.get_unused_cards(hole, board);
+    let mut points = vec![vec![0; cards.len()]; cards.len()];
+    for ((i, j), hand_strength) in get_two_card_hand_strengths(game_type, rules, &cards, board) {
+        points[i][j] = hand_strength.points;
+        points[j][i] = hand_strength.points;
+    }
+    let player_points = get_board_hand_strength(
+        game_type,
+        rules,
+        cards_to_mask(board),
+        &get_combination_masks(board, 3),
+        cards_to_mask(hole),
+    )
+    .points;
+
+    let mut better_points = HashSet::new();
+    let mut nut_rank = NutRank {
+        rank: 1,
+        better: 0,
+        ties: 0,
+        holdings: 0,
+    };
+    for holding in (0..cards.len()).combinations(game_type.hole_cards()) {
+        // A holding is as strong as the best two of its cards
+        let holding_points = holding
+            .into_iter()
+            .tuple_combinations()
+            .map(|(i, j)| points[i][j])
+            .max()
+            .unwrap();
+        if holding_points > player_points {
+            better_points.insert(holding_points);
+            nut_rank.better += 1;
+        } else if holding_points == player_points {
+            nut_rank.ties += 1;
+        }
+        nut_rank.holdings += 1;
+    }
+    nut_rank.rank = better_points.len() + 1;
+    Ok(nut_rank)
+}
+
+fn check_cards(
+    game_type: GameType,
+    rules: &Rules,
+    hole: &[Card],
+    board: &[Card],
+) -> Result<(), Error> {
+    rules.validate()?;
+    validate_cards(game_type, rules, hole, board)?;
+    if board.len() < 3 {
+        return Err(Error::NoFlop(board.len()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{game::HandCombination, rules::Ante};
+
+    #[test]
+    fn can_get_nuts() {
+        let nuts = get_nuts(
+            GameType::TexasHoldem,
+            &Rules::new(GameType::TexasHoldem),
+            &Card::from_cards_str("AhKh7h2c9d").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(nuts.hand_rank.hand_combination, HandCombination::Flush);
+        assert_eq!(nuts.hole_cards.len(), 1);
+        assert!(Card::from_cards_str("QhJh")
+            .unwrap()
+            .iter()
+            .all(|c| nuts.hole_cards[0].contains(c)));
+    }
+
+    #[test]
+    fn can_get_nut_rank() {
+        let nuts = get_nut_rank(
+            GameType::TexasHoldem,
+            &Rules::new(GameType::TexasHoldem),
+            &Card::from_cards_str("QhJh").unwrap(),
+            &Card::from_cards_str("AhKh7h2c9d").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            nuts,
+            NutRank {
+                rank: 1,
+                better: 0,
+                ties: 0,
+                holdings: 990,
+            }
+        );
+
+        // Only a set of aces beats the set of kings
+        let second_nuts = get_nut_rank(
+            GameType::TexasHoldem,
+            &Rules::new(GameType::TexasHoldem),
+            &Card::from_cards_str("KsKc").unwrap(),
+            &Card::from_cards_str("AsKd7h2c9d").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(second_nuts.rank, 2);
+        assert_eq!(second_nuts.better, 3);
+        assert!((second_nuts.top_fraction() - 3.0 / 990.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn can_get_omaha_nuts() {
+        let board = Card::from_cards_str("9s9d9h9c2s").unwrap();
+        let rules = Rules::new(GameType::Omaha);
+
+        // Quads on the board play as trips, aces in hand make the best full house
+        let nuts = get_nuts(GameType::Omaha, &rules, &board).unwrap();
+        assert_eq!(nuts.hand_rank.hand_combination, HandCombination::FullHouse);
+        assert_eq!(nuts.hole_cards.len(), 6);
+
+        let nut_rank = get_nut_rank(
+            GameType::Omaha,
+            &Rules::new(GameType::Omaha),
+            &Card::from_cards_str("AhAsKdKc").unwrap(),
+            &Card::from_cards_str("9s9d9h9c2s").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(nut_rank.rank, 1);
+        // Holdings with the two other aces tie
+        assert_eq!(nut_rank.ties, 41 * 40 / 2);
+        assert_eq!(nut_rank.holdings, 43 * 42 * 41 * 40 / 24);
+    }
+
+    #[test]
+    fn can_get_nuts_with_rules() {
+        let board = Card::from_cards_str("6s7d8hKcKd").unwrap();
+        let hole = Card::from_cards_str("9cTd").unwrap();
+        let rank = |ante| {
+            get_nut_rank(
+                GameType::ShortdeckHoldem,
+                &Rules::short_deck(ante),
+                &hole,
+                &board,
+            )
+            .unwrap()
+            .rank
+        };
+
+        // Trips of kings beat the straight when only antes are posted
+        assert!(rank(Ante::AnteOnly) > rank(Ante::ButtonBlind));
+    }
+
+    #[test]
+    fn cannot_get_nuts_without_flop_or_full_hand() {
+        let rules = Rules::new(GameType::TexasHoldem);
+
+        assert_eq!(
+            get_nuts(
+                GameType::TexasHoldem,
+                &rules,
+                &Card::from_cards_str("AhKh").unwrap()
+            )
+            .unwrap_err(),
+            Error::NoFlop(2)
+        );
+        assert_eq!(
+            get_nut_rank(
+                GameType::TexasHoldem,
+                &rules,
+                &Card::from_cards_str("Qh").unwrap(),
+                &Card::from_cards_str("AhKh7h").unwrap()
+            ),
+            Err(Error::UnknownCards)
+        );
+    }
+}
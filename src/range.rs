@@ -0,0 +1,407 @@
+use std::str::FromStr;
+
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
+use crate::{
+    card::{Card, Rank, Suit},
+    Error,
+};
+
+/// A set of concrete two card hands, parsed from standard range notation like
+/// `TT+, AQs-ATs, KJo, 76s, AhKh` or `random`. Every part can be followed by a
+/// weight like in `AsKs:0.5,AhKh:1`, parts without one have a weight of 1.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Range {
+    pub combos: Vec<Combo>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Combo {
+    pub cards: Vec<Card>,
+    /// Relative frequency of the combo in the range.
+    pub weight: f64,
+}
+
+/// Two ranks with an optional suitedness, `AKs`, `AKo`, `AK` or `TT`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct HandClass {
+    high: Rank,
+    low: Rank,
+    suited: Option<bool>,
+}
+
+impl HandClass {
+    fn is_pair(&self) -> bool {
+        self.high == self.low
+    }
+
+    fn with_ranks(&self, high: Rank, low: Rank) -> HandClass {
+        HandClass {
+            high,
+            low,
+            suited: self.suited,
+        }
+    }
+
+    fn get_combos(&self) -> Vec<Vec<Card>> {
+        if self.is_pair() {
+            return Suit::iter()
+                .combinations(2)
+                .map(|suits| {
+                    suits
+                        .into_iter()
+                        .map(|suit| Card {
+                            suit,
+                            rank: self.high,
+                        })
+                        .collect()
+                })
+                .collect();
+        }
+
+        Suit::iter()
+            .cartesian_product(Suit::iter())
+            .filter(|(high_suit, low_suit)| match self.suited {
+                Some(suited) => suited == (high_suit == low_suit),
+                None => true,
+            })
+            .map(|(high_suit, low_suit)| {
+                vec![
+                    Card {
+                        suit: high_suit,
+                        rank: self.high,
+                    },
+                    Card {
+                        suit: low_suit,
+                        rank: self.low,
+                    },
+                ]
+            })
+            .collect()
+    }
+}
+
+/// Token of a range that cannot be parsed and the reason.
+type TokenError = (String, &'static str);
+
+impl FromStr for HandClass {
+    type Err = TokenError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<String> = s.chars().map(String::from).collect();
+        if chars.len() != 2 && chars.len() != 3 {
+            return Err((
+                s.to_string(),
+                "Wrong size! Expected a hand like `AK`, `AKs`, `AKo` or `TT`",
+            ));
+        }
+        let parse_rank = |c: &String| Rank::from_str(c).map_err(|_| (c.clone(), "Unknown rank"));
+        let first = parse_rank(&chars[0])?;
+        let second = parse_rank(&chars[1])?;
+        let suited = match chars.get(2).map(|c| c.to_lowercase()).as_deref() {
+            None => None,
+            Some("s") => Some(true),
+            Some("o") => Some(false),
+            _ => return Err((chars[2].clone(), "Expected `s` or `o` after the ranks")),
+        };
+        if first == second && suited.is_some() {
+            return Err((s.to_string(), "Pairs cannot be suited or offsuit"));
+        }
+
+        Ok(HandClass {
+            high: if first > second { first } else { second },
+            low: if first > second { second } else { first },
+            suited,
+        })
+    }
+}
+
+/// Ranks from `from` up to and including `to`.
+fn ranks_between(from: Rank, to: Rank) -> impl Iterator<Item = Rank> {
+    Rank::iter().filter(move |r| *r >= from && *r <= to)
+}
+
+fn parse_part(part: &str) -> Result<Vec<Vec<Card>>, TokenError> {
+    if part.eq_ignore_ascii_case("random") {
+        return Ok(Card::get_all_cards().into_iter().combinations(2).collect());
+    }
+
+    if let Some(class) = part.strip_suffix('+') {
+        let class = HandClass::from_str(class)?;
+        let classes: Vec<HandClass> = if class.is_pair() {
+            ranks_between(class.high, Rank::Ace)
+                .map(|r| class.with_ranks(r, r))
+                .collect()
+        } else {
+            ranks_between(class.low, class.high)
+                .filter(|r| *r != class.high)
+                .map(|r| class.with_ranks(class.high, r))
+                .collect()
+        };
+        return Ok(classes.iter().flat_map(HandClass::get_combos).collect());
+    }
+
+    if let Some((from, to)) = part.split_once('-') {
+        let from = HandClass::from_str(from)?;
+        let to = HandClass::from_str(to)?;
+        if from.is_pair() != to.is_pair() || from.suited != to.suited {
+            return Err((
+                part.to_string(),
+                "Both ends of a dash range must be the same kind of hand",
+            ));
+        }
+        let classes: Vec<HandClass> = if from.is_pair() {
+            let (low, high) = if from.high < to.high {
+                (from.high, to.high)
+            } else {
+                (to.high, from.high)
+            };
+            ranks_between(low, high)
+                .map(|r| from.with_ranks(r, r))
+                .collect()
+        } else {
+            if from.high != to.high {
+                return Err((
+                    part.to_string(),
+                    "Both ends of a dash range must share the highest rank",
+                ));
+            }
+            let (low, high) = if from.low < to.low {
+                (from.low, to.low)
+            } else {
+                (to.low, from.low)
+            };
+            ranks_between(low, high)
+                .map(|r| from.with_ranks(from.high, r))
+                .collect()
+        };
+        return Ok(classes.iter().flat_map(HandClass::get_combos).collect());
+    }
+
+    if part.chars().count() == 4 {
+        let cards = Card::from_cards_str(part).map_err(|e| {
+            let token = match e {
+                Error::UnknownRank { rank, .. } => rank,
+                Error::UnknownSuit { suit, .. } => suit,
+                _ => part.to_string(),
+            };
+            (token, "Unknown card")
+        })?;
+        if cards[0] == cards[1] {
+            return Err((part.to_string(), "A combo cannot hold the same card twice"));
+        }
+        return Ok(vec![cards]);
+    }
+
+    Ok(HandClass::from_str(part)?.get_combos())
+}
+
+fn is_same_combo(a: &[Card], b: &[Card]) -> bool {
+    a.len() == b.len() && a.iter().all(|c| b.contains(c))
+}
+
+fn parse_weight(weight: &str) -> Result<f64, TokenError> {
+    match f64::from_str(weight.trim()) {
+        Ok(weight) if weight.is_finite() && weight >= 0.0 => Ok(weight),
+        _ => Err((
+            weight.trim().to_string(),
+            "Combo weights must be non-negative numbers",
+        )),
+    }
+}
+
+impl Range {
+    /// Number of concrete two card combos in the range.
+    pub fn combo_count(&self) -> usize {
+        self.combos.len()
+    }
+
+    /// Sum of the combo weights.
+    pub fn weighted_combo_count(&self) -> f64 {
+        self.combos.iter().map(|c| c.weight).sum()
+    }
+
+    /// Whether every combo has the same weight, so each is as likely as the others.
+    pub fn is_uniform(&self) -> bool {
+        self.combos
+            .iter()
+            .all(|c| c.weight == self.combos[0].weight)
+    }
+
+    /// Removes every combo holding one of `cards`, usually board and dead cards.
+    pub fn remove_conflicts(&mut self, cards: &[Card]) {
+        self.combos
+            .retain(|combo| !combo.cards.iter().any(|c| cards.contains(c)));
+    }
+}
+
+impl FromStr for Range {
+    type Err = Error;
+
+    /// Later parts override the weight of combos already in the range.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut combos: Vec<Combo> = vec![];
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let invalid_range = |(token, reason)| Error::InvalidRange {
+                part: part.to_string(),
+                token,
+                reason,
+            };
+            let (hands, weight) = match part.split_once(':') {
+                Some((hands, weight)) => {
+                    (hands.trim(), parse_weight(weight).map_err(invalid_range)?)
+                }
+                None => (part, 1.0),
+            };
+            for cards in parse_part(hands).map_err(invalid_range)? {
+                match combos.iter_mut().find(|c| is_same_combo(&c.cards, &cards)) {
+                    Some(combo) => combo.weight = weight,
+                    None => combos.push(Combo { cards, weight }),
+                }
+            }
+        }
+        combos.retain(|c| c.weight > 0.0);
+
+        if combos.is_empty() {
+            return Err(Error::InvalidRange {
+                part: s.to_string(),
+                token: s.to_string(),
+                reason: "Range does not hold any hand",
+            });
+        }
+        Ok(Range { combos })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_combo_count(s: &str) -> usize {
+        Range::from_str(s).unwrap().combo_count()
+    }
+
+    #[test]
+    fn can_parse_pairs() {
+        assert_eq!(get_combo_count("TT"), 6);
+        assert_eq!(get_combo_count("TT+"), 30);
+        assert_eq!(get_combo_count("22+"), 78);
+        assert_eq!(get_combo_count("TT-77"), 24);
+        assert_eq!(get_combo_count("77-TT"), 24);
+    }
+
+    #[test]
+    fn can_parse_suited_and_offsuit_hands() {
+        assert_eq!(get_combo_count("76s"), 4);
+        assert_eq!(get_combo_count("KJo"), 12);
+        assert_eq!(get_combo_count("AK"), 16);
+        assert_eq!(get_combo_count("KA"), 16);
+        assert_eq!(get_combo_count("AQs-ATs"), 12);
+        assert_eq!(get_combo_count("ATo+"), 48);
+        assert_eq!(get_combo_count("K2s+"), 44);
+    }
+
+    #[test]
+    fn can_parse_specific_combos_and_random() {
+        assert_eq!(
+            Range::from_str("AhKh").unwrap().combos,
+            vec![Combo {
+                cards: Card::from_cards_str("AhKh").unwrap(),
+                weight: 1.0
+            }]
+        );
+        assert_eq!(get_combo_count("random"), 1326);
+    }
+
+    #[test]
+    fn can_parse_combined_range() {
+        assert_eq!(get_combo_count("TT+, AQs-ATs, KJo, 76s"), 30 + 12 + 12 + 4);
+        assert_eq!(get_combo_count("AK, AKs, AhKh, KhAh"), 16);
+    }
+
+    #[test]
+    fn cannot_parse_invalid_range() {
+        assert!(Range::from_str("").is_err());
+        assert!(Range::from_str("TTs").is_err());
+        assert!(Range::from_str("AKx").is_err());
+        assert!(Range::from_str("AhAh").is_err());
+        assert!(Range::from_str("AKs-QJs").is_err());
+        assert!(Range::from_str("TT-AKs").is_err());
+        assert!(Range::from_str("AKs-AQo").is_err());
+        assert_eq!(
+            Range::from_str("TT+, AXs"),
+            Err(Error::InvalidRange {
+                part: "AXs".to_string(),
+                token: "X".to_string(),
+                reason: "Unknown rank"
+            })
+        );
+        assert_eq!(
+            Range::from_str("AKs-AQo"),
+            Err(Error::InvalidRange {
+                part: "AKs-AQo".to_string(),
+                token: "AKs-AQo".to_string(),
+                reason: "Both ends of a dash range must be the same kind of hand"
+            })
+        );
+        assert_eq!(
+            Range::from_str("AhKx:0.5"),
+            Err(Error::InvalidRange {
+                part: "AhKx:0.5".to_string(),
+                token: "x".to_string(),
+                reason: "Unknown card"
+            })
+        );
+        assert_eq!(
+            Range::from_str("AK:half"),
+            Err(Error::InvalidRange {
+                part: "AK:half".to_string(),
+                token: "half".to_string(),
+                reason: "Combo weights must be non-negative numbers"
+            })
+        );
+    }
+
+    #[test]
+    fn can_remove_conflicting_combos() {
+        let mut range = Range::from_str("AA, KQs").unwrap();
+        range.remove_conflicts(&Card::from_cards_str("AhKd").unwrap());
+
+        assert_eq!(range.combo_count(), 3 + 3);
+        assert!(range.combos.iter().all(|combo| !combo
+            .cards
+            .iter()
+            .any(|c| c.rank == Rank::Ace && c.suit == Suit::Hearts)));
+    }
+
+    #[test]
+    fn can_parse_weighted_range() {
+        let range = Range::from_str("AsKs:0.5,AhKh:1, QQ:0.25, AKo:0.35").unwrap();
+
+        assert_eq!(range.combo_count(), 2 + 6 + 12);
+        assert!((range.weighted_combo_count() - (1.5 + 6.0 * 0.25 + 12.0 * 0.35)).abs() < 1e-9);
+        assert_eq!(range.combos[0].weight, 0.5);
+        assert!(!range.is_uniform());
+        assert!(Range::from_str("AA:0.5, KK:0.5").unwrap().is_uniform());
+    }
+
+    #[test]
+    fn can_override_and_drop_weights() {
+        let range = Range::from_str("AA, AhAd:0.5, KK:0").unwrap();
+
+        assert_eq!(range.combo_count(), 6);
+        assert_eq!(
+            range
+                .combos
+                .iter()
+                .filter(|c| c.weight == 0.5)
+                .map(|c| c.cards.clone())
+                .collect::<Vec<Vec<Card>>>(),
+            vec![Card::from_cards_str("AhAd").unwrap()]
+        );
+        assert!(Range::from_str("AA:-1").is_err());
+        assert!(Range::from_str("AA:x").is_err());
+        assert!(Range::from_str("AA:0").is_err());
+    }
+}
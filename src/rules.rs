@@ -0,0 +1,271 @@
+use std::iter::once;
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    card::{Card, Rank},
+    game::HandCombination,
+    Error, GameType,
+};
+
+/// Straight the ace makes as its lowest card.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LowStraight {
+    /// A-2-3-4-5, a Five high straight.
+    AceToFive,
+    /// A-6-7-8-9, the lowest straight of the short deck, ranked as Nine high.
+    AceToNine,
+}
+
+impl LowStraight {
+    /// Ranks of the straight, ace included.
+    pub const fn ranks(&self) -> [Rank; 5] {
+        match self {
+            LowStraight::AceToFive => [Rank::Ace, Rank::Two, Rank::Three, Rank::Four, Rank::Five],
+            LowStraight::AceToNine => [Rank::Ace, Rank::Six, Rank::Seven, Rank::Eight, Rank::Nine],
+        }
+    }
+}
+
+/// How short deck games are posted, which decides whether trips beat a straight.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Ante {
+    /// Every player posts an ante only, three of a kind beats a straight.
+    AnteOnly,
+    /// The button posts a blind on top of the antes, a straight beats three of a
+    /// kind like in Texas Hold'em.
+    ButtonBlind,
+}
+
+/// How hands are ranked and which cards are dealt. Rules built by hand are
+/// checked with `validate` by the functions taking them.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Rules {
+    /// Hand combinations from the best to the worst.
+    pub ranking: [HandCombination; 10],
+    /// Lowest rank of the deck, every card from it up to the aces is dealt.
+    pub lowest_rank: Rank,
+    /// Straight of the ace with the four lowest ranks of the deck, checked
+    /// against `lowest_rank`.
+    pub low_straight: LowStraight,
+    /// How short deck games are posted, `None` for the other games. The ranking
+    /// it picks is kept in `ranking`, which decides how hands compare.
+    pub ante: Option<Ante>,
+}
+
+const HOLDEM_RANKING: [HandCombination; 10] = [
+    HandCombination::RoyalFlush,
+    HandCombination::StraightFlush,
+    HandCombination::Quads,
+    HandCombination::FullHouse,
+    HandCombination::Flush,
+    HandCombination::Straight,
+    HandCombination::TreeOfAKind,
+    HandCombination::TwoPairs,
+    HandCombination::OnePair,
+    HandCombination::HighCards,
+];
+
+const SHORT_DECK_RANKING: [HandCombination; 10] = [
+    HandCombination::RoyalFlush,
+    HandCombination::StraightFlush,
+    HandCombination::Quads,
+    HandCombination::Flush,
+    HandCombination::FullHouse,
+    HandCombination::Straight,
+    HandCombination::TreeOfAKind,
+    HandCombination::TwoPairs,
+    HandCombination::OnePair,
+    HandCombination::HighCards,
+];
+
+const SHORT_DECK_ANTE_ONLY_RANKING: [HandCombination; 10] = [
+    HandCombination::RoyalFlush,
+    HandCombination::StraightFlush,
+    HandCombination::Quads,
+    HandCombination::Flush,
+    HandCombination::FullHouse,
+    HandCombination::TreeOfAKind,
+    HandCombination::Straight,
+    HandCombination::TwoPairs,
+    HandCombination::OnePair,
+    HandCombination::HighCards,
+];
+
+impl Rules {
+    /// Standard rules of `game_type`, short deck games are played with a button
+    /// blind.
+    pub fn new(game_type: GameType) -> Rules {
+        match game_type {
+            GameType::ShortdeckHoldem => Rules::short_deck(Ante::ButtonBlind),
+            _ => Rules {
+                ranking: HOLDEM_RANKING,
+                lowest_rank: Rank::Two,
+                low_straight: LowStraight::AceToFive,
+                ante: None,
+            },
+        }
+    }
+
+    /// 36 card deck from the sixes up, where a flush beats a full house.
+    pub fn short_deck(ante: Ante) -> Rules {
+        Rules {
+            ranking: match ante {
+                Ante::AnteOnly => SHORT_DECK_ANTE_ONLY_RANKING,
+                Ante::ButtonBlind => SHORT_DECK_RANKING,
+            },
+            lowest_rank: Rank::Six,
+            low_straight: LowStraight::AceToNine,
+            ante: Some(ante),
+        }
+    }
+
+    /// Checks that `ranking` holds every hand combination exactly once and that
+    /// the low straight is made of the lowest ranks of the deck, as the evaluator
+    /// and the straights of `get_straights` rank it.
+    pub fn validate(&self) -> Result<(), Error> {
+        if !HOLDEM_RANKING.iter().all(|hc| self.ranking.contains(hc)) {
+            return Err(Error::InvalidRanking);
+        }
+        if self.low_straight.ranks()[1] != self.lowest_rank {
+            return Err(Error::InvalidLowStraight {
+                low_straight: self.low_straight,
+                lowest_rank: self.lowest_rank,
+            });
+        }
+        Ok(())
+    }
+
+    /// Every card of the deck.
+    pub fn get_deck(&self) -> Vec<Card> {
+        Card::get_all_cards()
+            .into_iter()
+            .filter(|c| c.rank >= self.lowest_rank)
+            .collect()
+    }
+
+    /// Cards of the deck that are neither in `hole` nor on `board`.
+    pub(crate) fn get_unused_cards(&self, hole: &[Card], board: &[Card]) -> Vec<Card> {
+        self.get_deck()
+            .into_iter()
+            .filter(|c| !hole.contains(c) && !board.contains(c))
+            .collect()
+    }
+
+    /// Every straight from the highest down, the one with a low ace last.
+    pub(crate) fn get_straights(&self) -> Vec<Vec<Rank>> {
+        self.get_straight_order()
+            .windows(5)
+            .map(<[Rank]>::to_vec)
+            .collect()
+    }
+
+    /// Ranks of the deck from the aces down, ending with the ace again as it
+    /// plays in the low straight.
+    pub(crate) fn get_straight_order(&self) -> Vec<Rank> {
+        Rank::iter()
+            .rev()
+            .filter(|r| *r >= self.lowest_rank)
+            .chain(once(self.low_straight.ranks()[0]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_get_deck() {
+        assert_eq!(Rules::new(GameType::TexasHoldem).get_deck().len(), 52);
+        assert_eq!(Rules::new(GameType::ShortdeckHoldem).get_deck().len(), 36);
+        assert_eq!(
+            Rules::new(GameType::ShortdeckHoldem).get_deck(),
+            Card::get_short_deck_cards()
+        );
+    }
+
+    #[test]
+    fn can_get_short_deck_rules() {
+        let position = |rules: &Rules, hand_combination| {
+            rules
+                .ranking
+                .iter()
+                .position(|hc| *hc == hand_combination)
+                .unwrap()
+        };
+        let ante_only = Rules::short_deck(Ante::AnteOnly);
+        let button_blind = Rules::short_deck(Ante::ButtonBlind);
+
+        assert!(
+            position(&ante_only, HandCombination::TreeOfAKind)
+                < position(&ante_only, HandCombination::Straight)
+        );
+        assert!(
+            position(&button_blind, HandCombination::Straight)
+                < position(&button_blind, HandCombination::TreeOfAKind)
+        );
+        assert!(
+            position(&button_blind, HandCombination::Flush)
+                < position(&button_blind, HandCombination::FullHouse)
+        );
+        assert_eq!(button_blind, Rules::new(GameType::ShortdeckHoldem));
+        assert_eq!(ante_only.ante, Some(Ante::AnteOnly));
+        assert_eq!(Rules::new(GameType::TexasHoldem).ante, None);
+    }
+
+    #[test]
+    fn can_validate_ranking() {
+        assert_eq!(Rules::new(GameType::TexasHoldem).validate(), Ok(()));
+        assert_eq!(Rules::short_deck(Ante::AnteOnly).validate(), Ok(()));
+
+        let mut rules = Rules::new(GameType::TexasHoldem);
+        rules.ranking[9] = HandCombination::OnePair;
+        assert_eq!(rules.validate(), Err(Error::InvalidRanking));
+    }
+
+    #[test]
+    fn can_validate_low_straight() {
+        let mut rules = Rules::new(GameType::TexasHoldem);
+        rules.low_straight = LowStraight::AceToNine;
+        assert_eq!(
+            rules.validate(),
+            Err(Error::InvalidLowStraight {
+                low_straight: LowStraight::AceToNine,
+                lowest_rank: Rank::Two,
+            })
+        );
+
+        let mut rules = Rules::short_deck(Ante::AnteOnly);
+        rules.low_straight = LowStraight::AceToFive;
+        assert_eq!(
+            rules.validate(),
+            Err(Error::InvalidLowStraight {
+                low_straight: LowStraight::AceToFive,
+                lowest_rank: Rank::Six,
+            })
+        );
+
+        // No straight starts from a seven with the ace below it
+        let mut rules = Rules::new(GameType::TexasHoldem);
+        rules.lowest_rank = Rank::Seven;
+        assert!(rules.validate().is_err());
+    }
+
+    #[test]
+    fn can_get_straights() {
+        let straights = Rules::new(GameType::TexasHoldem).get_straights();
+        assert_eq!(straights.len(), 10);
+        assert_eq!(
+            straights.last().unwrap(),
+            &[Rank::Five, Rank::Four, Rank::Three, Rank::Two, Rank::Ace]
+        );
+
+        let straights = Rules::new(GameType::ShortdeckHoldem).get_straights();
+        assert_eq!(straights.len(), 6);
+        assert_eq!(
+            straights.last().unwrap(),
+            &[Rank::Nine, Rank::Eight, Rank::Seven, Rank::Six, Rank::Ace]
+        );
+    }
+}
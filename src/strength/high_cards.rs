@@ -22,7 +22,7 @@ pub fn get_high_cards(
 
             cards
         }
-        _ => return cards.iter().take(5).cloned().collect(),
+        _ => cards.iter().take(5).cloned().collect(),
     }
 }
 
@@ -0,0 +1,300 @@
+use std::{cmp::Reverse, iter::repeat_n};
+
+use crate::{card::Rank, evaluator::CardMask, Card};
+
+/// Rank value when aces play low.
+pub fn low_rank_value(rank: Rank) -> u8 {
+    match rank {
+        Rank::Ace => 1,
+        _ => rank as u8,
+    }
+}
+
+/// Points of the best qualifying "eight or better" A-5 low (five different ranks,
+/// all eight or lower, aces low) of the cards in `mask`. Straights and flushes do
+/// not count against it.
+pub fn get_low_points(mask: CardMask) -> Option<u64> {
+    let ranks = (mask | mask >> 16 | mask >> 32 | mask >> 48) as u16;
+    // One bit per low value: twos to eights move up, the ace goes down to 1
+    let low_ranks = (ranks & 0x7f) << 2 | (ranks >> 12 & 1) << 1;
+    if low_ranks.count_ones() < 5 {
+        return None;
+    }
+
+    // The five lowest ranks, scored from the highest one down
+    let mut lowest = (1..=8).filter(|value| low_ranks & 1 << value != 0).take(5);
+    let mut values = [0; 5];
+    for value in values.iter_mut().rev() {
+        *value = lowest.next().unwrap();
+    }
+    Some(calc_low_value_points(values.iter().cloned()))
+}
+
+/// Points of a low hand given by the `low_rank_value` of its cards from the highest
+/// one down. A better (lower) hand gets more points, so low hands compare the same
+/// way as `HandStrength::points`.
+fn calc_low_value_points(values: impl Iterator<Item = u8>) -> u64 {
+    values.fold(0, |points, value| points * 14 + 14 - value as u64)
+}
+
+/// Number of cards of every rank, indexed by `low_rank_value`.
+pub fn get_low_rank_counts(cards: &[Card]) -> [usize; 14] {
+    let mut counts = [0; 14];
+    for c in cards {
+        counts[low_rank_value(c.rank) as usize] += 1;
+    }
+    counts
+}
+
+/// `get_low_rank_counts` of the cards in a mask.
+pub fn get_mask_low_rank_counts(mask: CardMask) -> [usize; 14] {
+    let mut counts = [0; 14];
+    for (value, count) in counts.iter_mut().enumerate().skip(1) {
+        let rank_bit = if value == 1 { 12 } else { value - 2 };
+        *count = (mask >> rank_bit & 0x0001_0001_0001_0001).count_ones() as usize;
+    }
+    counts
+}
+
+/// Orders cards for A-5 lowball comparison: bigger rank groups first, then from
+/// the highest rank down.
+fn sort_low_cards(cards: &mut [Card]) {
+    let counts = get_low_rank_counts(cards);
+    cards.sort_by_key(|c| {
+        let value = low_rank_value(c.rank);
+        Reverse((counts[value as usize], value))
+    });
+}
+
+/// Points of a Razz hand given by its `get_low_rank_counts`, where pairs count
+/// against the hand and straights and flushes are ignored. A better (lower) hand
+/// gets more points.
+pub fn calc_razz_points(counts: &[usize; 14]) -> u64 {
+    let distinct_ranks = counts.iter().filter(|count| **count > 0).count();
+    let max_same_rank = counts.iter().max().cloned().unwrap_or(0);
+    // No pair, one pair, two pairs, trips, full house and quads
+    let penalty = match (distinct_ranks, max_same_rank) {
+        (5, _) => 0,
+        (4, _) => 1,
+        (3, 2) => 2,
+        (3, _) => 3,
+        (2, 3) => 4,
+        _ => 5,
+    };
+
+    // The cards in `sort_low_cards` order: bigger groups first, higher ranks first
+    let values = (1..=max_same_rank).rev().flat_map(|count| {
+        (1..14u8)
+            .rev()
+            .filter(move |value| counts[*value as usize] == count)
+            .flat_map(move |value| repeat_n(value, count))
+    });
+    calc_low_value_points(values) + (6 - penalty) * 14u64.pow(5)
+}
+
+/// Best five card Razz hand (A-5 low without a qualifier) ordered for comparison.
+/// Cards are taken one at a time from the rank used the fewest times so far, the
+/// lowest rank on a draw: the distinct ranks from the lowest up, then the fewest
+/// paired cards with the lowest pairs. Among cards of the same rank the first
+/// ones are kept.
+pub fn get_razz_low(cards: &[Card]) -> Vec<Card> {
+    let mut used = get_razz_low_counts(&get_low_rank_counts(cards));
+    let mut low_cards: Vec<Card> = cards
+        .iter()
+        .filter(|c| {
+            let left = &mut used[low_rank_value(c.rank) as usize];
+            let keep = *left > 0;
+            *left = left.saturating_sub(1);
+            keep
+        })
+        .cloned()
+        .collect();
+    sort_low_cards(&mut low_cards);
+    low_cards
+}
+
+/// Rank counts of the best five card Razz hand among cards with rank `counts`,
+/// picked as in `get_razz_low`.
+pub fn get_razz_low_counts(counts: &[usize; 14]) -> [usize; 14] {
+    let mut used = [0; 14];
+    for _ in 0..5.min(counts.iter().sum()) {
+        let rank = (1..14)
+            .filter(|r| used[*r] < counts[*r])
+            .min_by_key(|r| used[*r])
+            .unwrap();
+        used[rank] += 1;
+    }
+    used
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+    use super::*;
+    use crate::evaluator::cards_to_mask;
+
+    fn calc_low_points(cards: &[Card]) -> u64 {
+        calc_low_value_points(cards.iter().map(|c| low_rank_value(c.rank)))
+    }
+
+    fn calc_card_razz_points(cards: &[Card]) -> u64 {
+        calc_razz_points(&get_low_rank_counts(cards))
+    }
+
+    #[test]
+    fn can_get_low() {
+        assert_eq!(
+            get_low_points(cards_to_mask(
+                &Card::from_cards_str("Kd8h5c4d2c2sAc").unwrap()
+            )),
+            Some(calc_low_points(
+                &Card::from_cards_str("8h5c4d2cAc").unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn can_get_wheel_low() {
+        assert_eq!(
+            get_low_points(cards_to_mask(&Card::from_cards_str("5s4s3s2sAs").unwrap())),
+            Some(calc_low_points(
+                &Card::from_cards_str("5s4s3s2sAs").unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn cannot_get_low_with_nine() {
+        assert_eq!(
+            get_low_points(cards_to_mask(&Card::from_cards_str("9h5c4d2cAc").unwrap())),
+            None
+        );
+    }
+
+    #[test]
+    fn cannot_get_low_with_pair() {
+        assert_eq!(
+            get_low_points(cards_to_mask(&Card::from_cards_str("7h5c5d2cAc").unwrap())),
+            None
+        );
+    }
+
+    #[test]
+    fn can_get_low_points() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let mut deck = Card::get_all_cards();
+        for _ in 0..10000 {
+            deck.shuffle(&mut rng);
+            let cards = &deck[..7];
+            // Every five cards of different low ranks, from the highest one down
+            let best = cards
+                .iter()
+                .cloned()
+                .combinations(5)
+                .filter(|low| {
+                    low.iter().all(|c| low_rank_value(c.rank) <= 8)
+                        && low.iter().map(|c| c.rank).all_unique()
+                })
+                .map(|mut low| {
+                    low.sort_by_key(|c| Reverse(low_rank_value(c.rank)));
+                    calc_low_points(&low)
+                })
+                .max();
+
+            assert_eq!(get_low_points(cards_to_mask(cards)), best, "{:?}", cards);
+        }
+    }
+
+    #[test]
+    fn can_get_low_rank_counts_of_mask() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let mut deck = Card::get_all_cards();
+        for _ in 0..10000 {
+            deck.shuffle(&mut rng);
+            let cards = &deck[..7];
+            let mask = cards_to_mask(cards);
+
+            assert_eq!(get_mask_low_rank_counts(mask), get_low_rank_counts(cards));
+        }
+    }
+
+    #[test]
+    fn better_low_gets_more_points() {
+        let wheel = calc_low_points(&Card::from_cards_str("5s4s3s2sAs").unwrap());
+        let six_four = calc_low_points(&Card::from_cards_str("6s4s3s2sAs").unwrap());
+        let eight_seven = calc_low_points(&Card::from_cards_str("8s7s3s2sAs").unwrap());
+        let eight_six = calc_low_points(&Card::from_cards_str("8s6s5s4s3s").unwrap());
+
+        assert!(wheel > six_four);
+        assert!(six_four > eight_six);
+        assert!(eight_six > eight_seven);
+    }
+
+    #[test]
+    fn can_get_razz_low() {
+        assert_eq!(
+            get_razz_low(&Card::from_cards_str("Kd8h5c4d2c2sAc").unwrap()),
+            Card::from_cards_str("8h5c4d2cAc").unwrap()
+        );
+    }
+
+    #[test]
+    fn can_get_razz_low_with_pair() {
+        assert_eq!(
+            get_razz_low(&Card::from_cards_str("KdKh5c5d2c2sAc").unwrap()),
+            Card::from_cards_str("2c2sKd5cAc").unwrap()
+        );
+    }
+
+    #[test]
+    fn better_razz_hand_gets_more_points() {
+        let king_low = calc_card_razz_points(&Card::from_cards_str("KdQh5c4d2c").unwrap());
+        let pair_of_aces = calc_card_razz_points(&Card::from_cards_str("AdAh4c3d2c").unwrap());
+        let pair_of_twos = calc_card_razz_points(&Card::from_cards_str("2d2h5c4dAc").unwrap());
+        let two_pairs = calc_card_razz_points(&Card::from_cards_str("2d2hAcAd3c").unwrap());
+
+        assert!(king_low > pair_of_aces);
+        assert!(pair_of_aces > pair_of_twos);
+        assert!(pair_of_twos > two_pairs);
+    }
+
+    #[test]
+    fn razz_low_is_the_best_five_cards() {
+        let mut rng = StdRng::seed_from_u64(7);
+        // Few ranks make trips, full houses and quads common
+        let few_ranks = Card::get_all_cards()
+            .into_iter()
+            .filter(|c| c.rank <= Rank::Three || c.rank == Rank::Ace)
+            .collect();
+        for mut deck in [Card::get_all_cards(), few_ranks] {
+            for _ in 0..10000 {
+                deck.shuffle(&mut rng);
+                let cards = &deck[..7];
+                let best = cards
+                    .iter()
+                    .cloned()
+                    .combinations(5)
+                    .map(|combination| calc_card_razz_points(&combination))
+                    .max()
+                    .unwrap();
+
+                assert_eq!(
+                    calc_card_razz_points(&get_razz_low(cards)),
+                    best,
+                    "{:?}",
+                    cards
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn can_get_razz_low_of_few_cards() {
+        assert_eq!(
+            get_razz_low(&Card::from_cards_str("KdKh2c").unwrap()),
+            Card::from_cards_str("KdKh2c").unwrap()
+        );
+    }
+}
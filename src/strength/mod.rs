@@ -1,8 +1,11 @@
 pub mod flush;
 pub mod full_house;
 pub mod high_cards;
+pub mod low;
 pub mod one_pair;
 pub mod quads;
+#[cfg(test)]
+pub mod short_deck;
 pub mod straight;
 pub mod straight_flush;
 pub mod three_of_a_kind;
@@ -53,17 +53,15 @@ pub fn get_one_pair(
                     None
                 } else {
                     op.sort_by(|a, b| b.rank.partial_cmp(&a.rank).unwrap());
-                    Some(pair.iter().chain(op.into_iter()).cloned().collect())
+                    Some(pair.iter().chain(op).cloned().collect())
                 }
             }
-            _ => {
-                return Some(
-                    pair.iter()
-                        .chain(cards.iter().filter(|c| c.rank != pair[0].rank).take(3))
-                        .cloned()
-                        .collect(),
-                )
-            }
+            _ => Some(
+                pair.iter()
+                    .chain(cards.iter().filter(|c| c.rank != pair[0].rank).take(3))
+                    .cloned()
+                    .collect(),
+            ),
         },
         None => None,
     }
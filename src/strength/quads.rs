@@ -70,15 +70,13 @@ pub fn get_quads(
             let highest_board_kicker = cards.iter().find(|c| c.rank != matches[0][0].rank);
 
             match highest_board_kicker {
-                Some(highest_board_kicker) => {
-                    return Some(
-                        matches[0]
-                            .iter()
-                            .chain([*highest_board_kicker].iter())
-                            .cloned()
-                            .collect(),
-                    );
-                }
+                Some(highest_board_kicker) => Some(
+                    matches[0]
+                        .iter()
+                        .chain([*highest_board_kicker].iter())
+                        .cloned()
+                        .collect(),
+                ),
                 None => Some(matches[0].to_vec()),
             }
         }
@@ -0,0 +1,158 @@
+use strum::IntoEnumIterator;
+
+use crate::{card::Rank, game::HandCombination, rules::Rules, Card};
+
+/// Best short deck hand of `cards` and points to compare it, worked out from
+/// plain rank and suit counts. It shares no code with `evaluator`, which it is
+/// the reference for.
+pub fn get_short_deck_hand(cards: &[Card], rules: &Rules) -> (HandCombination, u64) {
+    // Every rank of the deck from the highest down
+    let deck_ranks: Vec<Rank> = Rank::iter()
+        .rev()
+        .filter(|r| *r >= rules.lowest_rank)
+        .collect();
+    let count = |rank: Rank| cards.iter().filter(|c| c.rank == rank).count();
+    let ranks_with = |size: usize| -> Vec<Rank> {
+        deck_ranks
+            .iter()
+            .filter(|r| count(**r) >= size)
+            .cloned()
+            .collect()
+    };
+    let kickers = |used: &[Rank], size: usize| -> Vec<Rank> {
+        ranks_with(1)
+            .into_iter()
+            .filter(|r| !used.contains(r))
+            .take(size)
+            .collect()
+    };
+    let get_straight_high = |ranks: &[Rank]| -> Option<Rank> {
+        deck_ranks
+            .windows(5)
+            .find(|straight| straight.iter().all(|r| ranks.contains(r)))
+            .map(|straight| straight[0])
+            .or_else(|| {
+                let low_straight = rules.low_straight.ranks();
+                if low_straight.iter().all(|r| ranks.contains(r)) {
+                    Some(low_straight[4])
+                } else {
+                    None
+                }
+            })
+    };
+
+    let flush: Option<Vec<Rank>> = cards
+        .iter()
+        .map(|c| c.suit)
+        .find(|suit| cards.iter().filter(|c| c.suit == *suit).count() >= 5)
+        .map(|suit| {
+            deck_ranks
+                .iter()
+                .filter(|r| cards.iter().any(|c| c.suit == suit && c.rank == **r))
+                .cloned()
+                .collect()
+        });
+    let straight_flush_high = flush.as_deref().and_then(get_straight_high);
+    let quads = ranks_with(4);
+    let trips = ranks_with(3);
+    let pairs = ranks_with(2);
+
+    for (i, hand_combination) in rules.ranking.iter().enumerate() {
+        let tie_breakers: Option<Vec<Rank>> = match hand_combination {
+            HandCombination::RoyalFlush => straight_flush_high
+                .filter(|high| *high == Rank::Ace)
+                .map(|high| vec![high]),
+            HandCombination::StraightFlush => straight_flush_high.map(|high| vec![high]),
+            HandCombination::Quads => quads.first().map(|q| vec![*q, kickers(&[*q], 1)[0]]),
+            HandCombination::FullHouse => trips
+                .first()
+                .and_then(|t| pairs.iter().find(|p| *p != t).map(|p| vec![*t, *p])),
+            HandCombination::Flush => flush.as_ref().map(|f| f[..5].to_vec()),
+            HandCombination::Straight => get_straight_high(&ranks_with(1)).map(|high| vec![high]),
+            HandCombination::TreeOfAKind => trips
+                .first()
+                .map(|t| std::iter::once(*t).chain(kickers(&[*t], 2)).collect()),
+            HandCombination::TwoPairs if pairs.len() >= 2 => Some(
+                pairs[..2]
+                    .iter()
+                    .cloned()
+                    .chain(kickers(&pairs[..2], 1))
+                    .collect(),
+            ),
+            HandCombination::OnePair => pairs
+                .first()
+                .map(|p| std::iter::once(*p).chain(kickers(&[*p], 3)).collect()),
+            HandCombination::HighCards => Some(kickers(&[], 5)),
+            _ => None,
+        };
+
+        if let Some(tie_breakers) = tie_breakers {
+            let points = (0..5).fold((rules.ranking.len() - i) as u64, |points, j| {
+                points * 16 + tie_breakers.get(j).map_or(0, |r| *r as u64)
+            });
+            return (*hand_combination, points);
+        }
+    }
+
+    unreachable!("high cards always match")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Ante;
+
+    #[test]
+    fn can_get_short_deck_hands() {
+        for (cards, hand_combination) in [
+            ("AhKhQhJhTh6c6d", HandCombination::RoyalFlush),
+            ("Ah6h7h8h9hKcKd", HandCombination::StraightFlush),
+            ("9c9d9h9s6c7d8h", HandCombination::Quads),
+            ("6c8cTcQcAcAsAd", HandCombination::Flush),
+            ("9c9d9hAsAc7d8h", HandCombination::FullHouse),
+            ("Ac6d7h8s9cKdKh", HandCombination::Straight),
+            ("9c9d9hAsKc7d6h", HandCombination::TreeOfAKind),
+            ("9c9dAhAsKcKd6h", HandCombination::TwoPairs),
+            ("9c9dAhQsKc7d6h", HandCombination::OnePair),
+            ("9c8dAhQsKc7dTh", HandCombination::HighCards),
+        ] {
+            assert_eq!(
+                get_short_deck_hand(
+                    &Card::from_cards_str(cards).unwrap(),
+                    &Rules::short_deck(Ante::ButtonBlind)
+                )
+                .0,
+                hand_combination,
+                "{}",
+                cards
+            );
+        }
+    }
+
+    #[test]
+    fn can_compare_short_deck_hands() {
+        let points = |cards| {
+            get_short_deck_hand(
+                &Card::from_cards_str(cards).unwrap(),
+                &Rules::short_deck(Ante::ButtonBlind),
+            )
+            .1
+        };
+        let ante_only_points = |cards| {
+            get_short_deck_hand(
+                &Card::from_cards_str(cards).unwrap(),
+                &Rules::short_deck(Ante::AnteOnly),
+            )
+            .1
+        };
+
+        // A-6-7-8-9 is the lowest straight and straight flush
+        assert!(points("Ac6d7h8s9c") < points("6c7d8h9sTc"));
+        assert!(points("Ah6h7h8h9h") < points("6h7h8h9hTh"));
+        assert!(points("Ah6h7h8h9h") > points("9c9d9h9sAc"));
+        // A flush beats a full house
+        assert!(points("6c8cTcQcAc") > points("AcAdAhKsKc"));
+        assert!(points("Ac6d7h8s9c") > points("AcAdAhKsQc"));
+        assert!(ante_only_points("Ac6d7h8s9c") < ante_only_points("6c6d6hKsQc"));
+    }
+}
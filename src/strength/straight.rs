@@ -1,16 +1,19 @@
 use std::collections::HashMap;
 
+use itertools::Itertools;
+use strum::IntoEnumIterator;
+
 use crate::{card::Rank, Card, GameType};
 
 pub fn get_straight(
     game_type: GameType,
     cards: &[Card],
     rank_map: &HashMap<Rank, Vec<Card>>,
+    player_cards: &[Card],
 ) -> Option<Vec<Card>> {
     if rank_map.keys().len() < 5 {
         return None;
     }
-    let is_omaha = game_type == GameType::Omaha;
 
     let lowest_rank = if game_type == GameType::ShortdeckHoldem {
         Rank::Six
@@ -18,56 +21,74 @@ pub fn get_straight(
         Rank::Two
     };
 
+    if game_type == GameType::Omaha {
+        return get_omaha_straight(lowest_rank, rank_map, player_cards);
+    }
+
     let mut card_match: Vec<&Card> = Vec::with_capacity(5);
-    let mut card_match_omaha: Vec<Vec<&Card>> = vec![];
     for (i, card) in cards.iter().enumerate() {
         if i == 0 {
             card_match = vec![card];
-
-            if is_omaha {
-                card_match_omaha = vec![vec![card]];
-            }
             continue;
         }
 
         let prev_card = *card_match.last().unwrap();
 
-        if card.rank == prev_card.rank {
-            if is_omaha {
-                card_match_omaha.iter_mut().last().unwrap().push(card);
-            }
-        } else if card.rank as u8 + 1 == prev_card.rank as u8 {
+        if card.rank as u8 + 1 == prev_card.rank as u8 {
             card_match.push(card);
-            if is_omaha {
-                card_match_omaha.push(vec![card]);
-            }
             if card.rank == lowest_rank && cards[0].rank == Rank::Ace && card_match.len() == 4 {
                 card_match.push(&cards[0]);
             }
-        } else if card_match.len() < 5 {
+        } else if card.rank != prev_card.rank && card_match.len() < 5 {
             card_match = vec![card];
-            if is_omaha {
-                card_match_omaha = vec![vec![card]];
-            }
             continue;
         }
 
         if card_match.len() >= 5 {
-            match game_type {
-                GameType::Omaha => {
-                    panic!("Not yet implemented :/");
-                    // TODO add logic
-                }
-                _ => {
-                    return Some(card_match.into_iter().cloned().collect());
-                }
-            }
+            return Some(card_match.into_iter().cloned().collect());
         }
     }
 
     None
 }
 
+/// Omaha straights have to be made from exactly two hole cards and three
+/// board cards, so every straight is checked from the highest down until one
+/// can be built with that split.
+fn get_omaha_straight(
+    lowest_rank: Rank,
+    rank_map: &HashMap<Rank, Vec<Card>>,
+    player_cards: &[Card],
+) -> Option<Vec<Card>> {
+    let ranks: Vec<Rank> = Rank::iter().rev().filter(|r| *r >= lowest_rank).collect();
+    let wheel: Vec<Rank> = ranks[ranks.len() - 4..]
+        .iter()
+        .cloned()
+        .chain(std::iter::once(Rank::Ace))
+        .collect();
+
+    ranks
+        .windows(5)
+        .map(|straight| straight.to_vec())
+        .chain(std::iter::once(wheel))
+        .find_map(|straight| {
+            (0..5).combinations(2).find_map(|from_hand| {
+                straight
+                    .iter()
+                    .enumerate()
+                    .map(|(i, rank)| {
+                        let use_player_card = from_hand.contains(&i);
+                        rank_map
+                            .get(rank)?
+                            .iter()
+                            .find(|c| player_cards.contains(c) == use_player_card)
+                            .cloned()
+                    })
+                    .collect::<Option<Vec<Card>>>()
+            })
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::card::{Rank, Suit};
@@ -87,7 +108,8 @@ mod tests {
             get_straight(
                 GameType::TexasHoldem,
                 &Card::from_cards_str("8h7c6c5c4d").unwrap(),
-                &rank_map
+                &rank_map,
+                &[]
             ),
             Some(vec![
                 Card {
@@ -128,7 +150,8 @@ mod tests {
             get_straight(
                 GameType::TexasHoldem,
                 &Card::from_cards_str("AdKc5s4s3s2s").unwrap(),
-                &rank_map
+                &rank_map,
+                &[]
             ),
             Some(vec![
                 Card {
@@ -169,7 +192,8 @@ mod tests {
             get_straight(
                 GameType::TexasHoldem,
                 &Card::from_cards_str("AdKc5s4s3s3c2s").unwrap(),
-                &rank_map
+                &rank_map,
+                &[]
             ),
             Some(vec![
                 Card {
@@ -211,7 +235,8 @@ mod tests {
             get_straight(
                 GameType::TexasHoldem,
                 &Card::from_cards_str("AdKc6c5s4s3s3c2s").unwrap(),
-                &rank_map
+                &rank_map,
+                &[]
             ),
             Some(vec![
                 Card {
@@ -251,9 +276,124 @@ mod tests {
             get_straight(
                 GameType::TexasHoldem,
                 &Card::from_cards_str("Ac7c6c5c4c").unwrap(),
-                &rank_map
+                &rank_map,
+                &[]
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn can_get_straight_omaha() {
+        let rank_map = hashmap! {
+            Rank::King => Card::from_cards_str("Kd").unwrap(),
+            Rank::Ten => Card::from_cards_str("Ts").unwrap(),
+            Rank::Nine => Card::from_cards_str("9h").unwrap(),
+            Rank::Eight => Card::from_cards_str("8c").unwrap(),
+            Rank::Seven => Card::from_cards_str("7d").unwrap(),
+            Rank::Six => Card::from_cards_str("6h").unwrap(),
+            Rank::Five => Card::from_cards_str("5c").unwrap(),
+            Rank::Two => Card::from_cards_str("2d2s").unwrap(),
+        };
+        assert_eq!(
+            get_straight(
+                GameType::Omaha,
+                &Card::from_cards_str("KdTs9h8c7d6h5c2d2s").unwrap(),
+                &rank_map,
+                &Card::from_cards_str("9h8c2d2s").unwrap()
+            ),
+            Some(Card::from_cards_str("Ts9h8c7d6h").unwrap())
+        );
+    }
+
+    #[test]
+    fn cannot_get_straight_omaha_with_one_player_card() {
+        let rank_map = hashmap! {
+            Rank::King => Card::from_cards_str("Kd").unwrap(),
+            Rank::Nine => Card::from_cards_str("9h").unwrap(),
+            Rank::Eight => Card::from_cards_str("8s").unwrap(),
+            Rank::Seven => Card::from_cards_str("7c").unwrap(),
+            Rank::Six => Card::from_cards_str("6d").unwrap(),
+            Rank::Five => Card::from_cards_str("5h").unwrap(),
+            Rank::Two => Card::from_cards_str("2c2d2s").unwrap(),
+        };
+        assert_eq!(
+            get_straight(
+                GameType::Omaha,
+                &Card::from_cards_str("Kd9h8s7c6d5h2c2d2s").unwrap(),
+                &rank_map,
+                &Card::from_cards_str("9h2c2d2s").unwrap()
             ),
             None
         );
     }
+
+    #[test]
+    fn cannot_get_straight_omaha_from_board_only() {
+        let rank_map = hashmap! {
+            Rank::Ace => Card::from_cards_str("Ah").unwrap(),
+            Rank::King => Card::from_cards_str("Kd").unwrap(),
+            Rank::Queen => Card::from_cards_str("QcQs").unwrap(),
+            Rank::Nine => Card::from_cards_str("9s").unwrap(),
+            Rank::Eight => Card::from_cards_str("8c").unwrap(),
+            Rank::Seven => Card::from_cards_str("7d").unwrap(),
+            Rank::Six => Card::from_cards_str("6h").unwrap(),
+            Rank::Five => Card::from_cards_str("5c").unwrap(),
+        };
+        assert_eq!(
+            get_straight(
+                GameType::Omaha,
+                &Card::from_cards_str("AhKdQcQs9s8c7d6h5c").unwrap(),
+                &rank_map,
+                &Card::from_cards_str("AhKdQcQs").unwrap()
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn can_get_lower_straight_omaha_when_highest_is_illegal() {
+        let rank_map = hashmap! {
+            Rank::King => Card::from_cards_str("KdKc").unwrap(),
+            Rank::Nine => Card::from_cards_str("9d").unwrap(),
+            Rank::Eight => Card::from_cards_str("8c").unwrap(),
+            Rank::Seven => Card::from_cards_str("7s").unwrap(),
+            Rank::Six => Card::from_cards_str("6d").unwrap(),
+            Rank::Five => Card::from_cards_str("5h").unwrap(),
+            Rank::Four => Card::from_cards_str("4h").unwrap(),
+            Rank::Three => Card::from_cards_str("3c").unwrap(),
+        };
+        assert_eq!(
+            get_straight(
+                GameType::Omaha,
+                &Card::from_cards_str("KdKc9d8c7s6d5h4h3c").unwrap(),
+                &rank_map,
+                &Card::from_cards_str("4h3cKdKc").unwrap()
+            ),
+            Some(Card::from_cards_str("7s6d5h4h3c").unwrap())
+        );
+    }
+
+    #[test]
+    fn can_get_straight_omaha_low_ace() {
+        let rank_map = hashmap! {
+            Rank::Ace => Card::from_cards_str("Ah").unwrap(),
+            Rank::King => Card::from_cards_str("KdKc").unwrap(),
+            Rank::Jack => Card::from_cards_str("Jd").unwrap(),
+            Rank::Nine => Card::from_cards_str("9c").unwrap(),
+            Rank::Five => Card::from_cards_str("5h").unwrap(),
+            Rank::Four => Card::from_cards_str("4d").unwrap(),
+            Rank::Three => Card::from_cards_str("3s").unwrap(),
+            Rank::Two => Card::from_cards_str("2c").unwrap(),
+        };
+        assert_eq!(
+            get_straight(
+                GameType::Omaha,
+                &Card::from_cards_str("AhKdKcJd9c5h4d3s2c").unwrap(),
+                &rank_map,
+                &Card::from_cards_str("Ah2cKdKc").unwrap()
+            ),
+            Some(Card::from_cards_str("5h4d3s2cAh").unwrap())
+        );
+    }
 }
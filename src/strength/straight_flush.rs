@@ -75,7 +75,7 @@ mod tests {
             Suit::Clubs => Card::from_cards_str("8c7c6c5c4c").unwrap(),
         };
         assert_eq!(
-            get_straight_flush(GameType::TexasHoldem, &suit_map, &vec![]),
+            get_straight_flush(GameType::TexasHoldem, &suit_map, &[]),
             Some(vec![
                 Card {
                     suit: Suit::Clubs,
@@ -56,25 +56,23 @@ pub fn get_three_of_a_kind(
                 }
 
                 if !op.is_empty() {
-                    return Some(three_match.iter().chain(op.into_iter()).cloned().collect());
+                    return Some(three_match.iter().chain(op).cloned().collect());
                 }
             }
             None
         }
-        _ => {
-            return Some(
-                matches[0]
-                    .iter()
-                    .chain(
-                        cards
-                            .iter()
-                            .filter(|c| c.rank != matches[0][0].rank)
-                            .take(2),
-                    )
-                    .cloned()
-                    .collect(),
-            )
-        }
+        _ => Some(
+            matches[0]
+                .iter()
+                .chain(
+                    cards
+                        .iter()
+                        .filter(|c| c.rank != matches[0][0].rank)
+                        .take(2),
+                )
+                .cloned()
+                .collect(),
+        ),
     }
 }
 
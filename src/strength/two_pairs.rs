@@ -64,21 +64,19 @@ pub fn get_two_pairs(
 
             None
         }
-        _ => {
-            return Some(
-                pairs[0]
-                    .iter()
-                    .chain(pairs[1].iter())
-                    .chain(
-                        cards
-                            .iter()
-                            .filter(|c| c.rank != pairs[0][0].rank && c.rank != pairs[1][0].rank)
-                            .take(1),
-                    )
-                    .cloned()
-                    .collect(),
-            )
-        }
+        _ => Some(
+            pairs[0]
+                .iter()
+                .chain(pairs[1].iter())
+                .chain(
+                    cards
+                        .iter()
+                        .filter(|c| c.rank != pairs[0][0].rank && c.rank != pairs[1][0].rank)
+                        .take(1),
+                )
+                .cloned()
+                .collect(),
+        ),
     }
 }
 
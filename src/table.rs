@@ -1,29 +1,124 @@
 use itertools::Itertools;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, Rng, SeedableRng};
 use rayon::iter::ParallelIterator;
 use rayon::prelude::*;
-use std::{collections::HashMap, sync::atomic::AtomicU64, time::Instant};
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::{
+    evaluator::{card_to_mask, cards_to_mask, get_card_masks, CardMask},
     game::{self, HandCombination, HandStrength},
-    player::Player,
-    Card, Cards, GameType,
+    range::{Combo, Range},
+    Card, Error, GameType, Rules,
 };
 
+/// What is known about the cards of a player.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Seat {
+    /// Known cards of the player, the missing ones are dealt from the deck. An
+    /// empty hand is a random opponent.
+    Hand(Vec<Card>),
+    /// Any two card combo of the range, a new one is picked for every runout.
+    Range(Range),
+}
+
+impl Seat {
+    fn known_cards(&self) -> &[Card] {
+        match self {
+            Seat::Hand(hand) => hand,
+            Seat::Range(_) => &[],
+        }
+    }
+}
+
+impl FromStr for Seat {
+    type Err = Error;
+
+    /// Exact cards like `AcKh` take precedence, anything else is read as a range.
+    /// When neither parses, strings without range syntax report the card error.
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match Card::from_cards_str(s) {
+            Ok(cards) => Ok(Seat::Hand(cards)),
+            Err(card_error) => match Range::from_str(s) {
+                Ok(range) => Ok(Seat::Range(range)),
+                Err(_)
+                    if !s.contains(&[',', '+', '-', ':'][..])
+                        && s.chars().count().is_multiple_of(2) =>
+                {
+                    Err(card_error)
+                }
+                Err(range_error) => Err(range_error),
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Table {
-    players: Vec<Player>,
+    game_type: GameType,
+    rules: Rules,
+    seats: Vec<Seat>,
     community_cards: Vec<Card>,
     dead_cards: Vec<Card>,
 }
 
+/// Results of one combo of a range seat.
+#[derive(Debug, Clone)]
+pub struct ComboResult {
+    pub hand: Vec<Card>,
+    /// Weight of the combo in the range.
+    pub weight: f64,
+    /// Runouts the seat was dealt this combo.
+    pub iterations: u64,
+    /// Sum of the weights of these runouts, see `PlayerResult`.
+    pub(crate) deal_weight: f64,
+    /// Sum of the weighted pot fractions won with this combo.
+    pub(crate) pot_share: f64,
+    /// Average pot share of the combo.
+    pub equity: f64,
+}
+
+/// Results of a seat. Runouts are counted by their weight: enumerated runouts of
+/// weighted ranges weigh the product of their combo weights, scaled so that all of
+/// them weigh `iterations` together. Sampled combos are already drawn in
+/// proportion to their weights, so sampled runouts and runouts of ranges without
+/// different weights weigh 1.
 #[derive(Debug, Clone)]
 pub struct PlayerResult {
+    /// Known cards of the player, empty for range seats.
     pub hand: Vec<Card>,
-    pub wins: u64,
-    pub ties: u64,
-    pub ranks: HashMap<HandCombination, u64>,
+    pub wins: f64,
+    pub ties: f64,
+    pub ranks: HashMap<HandCombination, f64>,
+    /// Times the player had the best high hand, alone or tied.
+    pub high_wins: f64,
+    /// Times the player had the best qualifying low hand, alone or tied.
+    pub low_wins: f64,
+    /// Times the player won the whole pot alone, hi-lo games only.
+    pub scoops: f64,
+    /// Times the player split one half of the pot with another player and won
+    /// nothing of the other half, hi-lo games only.
+    pub quartered: f64,
+    /// Sum of the weighted pot fractions won over all iterations.
+    pub(crate) pot_share: f64,
+    /// Sum of the weighted squared pot fractions, used for the standard error.
+    pub(crate) pot_share_squares: f64,
+    /// Average pot share, wins plus `1/k` of the pot for every `k`-way tie.
+    pub equity: f64,
+    /// Standard error of the equity (average pot share), 0 when enumerated.
+    pub std_error: f64,
+    /// 95% confidence interval of the equity.
+    pub confidence_interval: (f64, f64),
+    /// Results of every combo of a range seat that does not use known cards,
+    /// empty for exact hands.
+    pub combo_results: Vec<ComboResult>,
 }
 
 #[derive(Debug)]
@@ -34,225 +129,2581 @@ pub struct Result {
     pub time_in_ms: u64,
 }
 
+/// Equities of the players after one way to deal the next street.
+#[derive(Debug, Clone)]
+pub struct StreetResult {
+    /// Community cards dealt on the street, with those of the streets before it
+    /// that were not on the board yet.
+    pub cards: Vec<Card>,
+    /// Equity of every player with these cards on the board.
+    pub equities: Vec<f64>,
+}
+
+#[derive(Debug)]
+pub struct StreetBreakdown {
+    /// Community cards on the board after the street.
+    pub board_cards: usize,
+    pub street_results: Vec<StreetResult>,
+    /// Streets dealt but left out of `street_results` because they leave a range
+    /// seat without any combo.
+    pub blocked: u64,
+    /// Whether the streets are a random sample of every possible one.
+    pub approximate: bool,
+    pub time_in_ms: u64,
+}
+
+impl StreetBreakdown {
+    /// Number of streets giving `player` an equity in each of `buckets` equally
+    /// wide buckets from 0 to 1. Equities of 1 count in the last bucket, no
+    /// buckets give an empty histogram.
+    pub fn get_equity_histogram(&self, player: usize, buckets: usize) -> Vec<u64> {
+        let mut histogram = vec![0; buckets];
+        if buckets == 0 {
+            return histogram;
+        }
+        for street_result in &self.street_results {
+            let bucket = (street_result.equities[player] * buckets as f64) as usize;
+            histogram[bucket.min(buckets - 1)] += 1;
+        }
+        histogram
+    }
+
+    /// Average equity of `player` over the streets, `None` without any street.
+    pub fn get_average_equity(&self, player: usize) -> Option<f64> {
+        if self.street_results.is_empty() {
+            return None;
+        }
+        let total: f64 = self
+            .street_results
+            .iter()
+            .map(|sr| sr.equities[player])
+            .sum();
+        Some(total / self.street_results.len() as f64)
+    }
+}
+
+/// What one community card does for the players.
+#[derive(Debug, Clone)]
+pub struct Out {
+    pub card: Card,
+    /// Players winning part of the pot, more than one when they split it.
+    pub winners: Vec<usize>,
+    /// Pot fraction of every player.
+    pub pot_shares: Vec<f64>,
+    /// Hand of every player with the card on the board.
+    pub hand_combinations: Vec<HandCombination>,
+}
+
+/// Community cards giving every player the same equity.
+#[derive(Debug, Clone)]
+pub struct EquityGroup {
+    pub equities: Vec<f64>,
+    pub cards: Vec<Card>,
+}
+
+/// How the first player does against every holding of the second one, on the
+/// board and after the cards to come. Shares are weighted by the combo weights.
+#[derive(Debug, Clone)]
+pub struct HandPotential {
+    /// Share of the holdings the player is ahead of on the board.
+    pub ahead: f64,
+    pub tied: f64,
+    pub behind: f64,
+    /// Chance to end up ahead when behind or tied, ties counting half.
+    pub positive_potential: f64,
+    /// Chance to end up behind when ahead or tied, ties counting half.
+    pub negative_potential: f64,
+    /// Number of holdings of the opponent, saturating at `u64::MAX`.
+    pub holdings: u64,
+    /// Whether the holdings or the runouts of some holding were sampled.
+    pub approximate: bool,
+    pub time_in_ms: u64,
+}
+
+impl HandPotential {
+    /// Share of the holdings beaten on the board, ties counting half.
+    pub fn get_hand_strength(&self) -> f64 {
+        self.ahead + self.tied / 2.0
+    }
+
+    /// Hand strength adjusted by the chances to fall behind or to come from behind.
+    pub fn get_effective_hand_strength(&self) -> f64 {
+        let hand_strength = self.get_hand_strength();
+        hand_strength * (1.0 - self.negative_potential)
+            + (1.0 - hand_strength) * self.positive_potential
+    }
+}
+
+/// Combo picked for every seat, `None` for exact hands, and the dealt cards.
+type Deal = (Vec<Option<usize>>, Vec<CardMask>);
+
 impl Table {
+    /// Checks that the cards can be dealt in `game_type`: hand and board sizes,
+    /// no card used twice and enough cards left for every runout. Hands are
+    /// ranked by the standard rules of the game.
     pub fn new(
-        player_hands: Vec<Cards>,
+        game_type: GameType,
+        seats: Vec<Seat>,
         community_cards: Vec<Card>,
         dead_cards: Vec<Card>,
-    ) -> Table {
-        Table {
-            players: player_hands
-                .into_iter()
-                .map(|cards| Player::new(cards.cards))
-                .collect(),
+    ) -> std::result::Result<Table, Error> {
+        let table = Table {
+            game_type,
+            rules: Rules::new(game_type),
+            seats,
             community_cards,
             dead_cards,
+        };
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// Plays the table with other rules, checked again as the deck may change.
+    pub fn with_rules(self, rules: Rules) -> std::result::Result<Table, Error> {
+        let table = Table { rules, ..self };
+        table.validate()?;
+        Ok(table)
+    }
+
+    fn get_unused_cards(&self) -> Vec<Card> {
+        self.rules
+            .get_deck()
+            .into_iter()
+            .filter(|c| {
+                !self.dead_cards.contains(c)
+                    && !self.community_cards.contains(c)
+                    && !self.seats.iter().any(|s| s.known_cards().contains(c))
+            })
+            .collect()
+    }
+
+    /// Combos of every range seat that only use unused cards, empty for exact hands.
+    fn get_range_combos(&self) -> Vec<Vec<Combo>> {
+        let unused_cards = self.get_unused_cards();
+        self.seats
+            .iter()
+            .map(|seat| match seat {
+                Seat::Hand(_) => vec![],
+                Seat::Range(range) => range
+                    .combos
+                    .iter()
+                    .filter(|combo| combo.cards.iter().all(|c| unused_cards.contains(c)))
+                    .cloned()
+                    .collect(),
+            })
+            .collect()
+    }
+
+    pub fn get_results(&self, limit: u64, seed: Option<u64>) -> Result {
+        let start_instant = Instant::now();
+        let range_combos = self.get_range_combos();
+
+        let deal_count = self.get_deal_count(&range_combos, limit);
+
+        // Enumerate every runout when they fit in the limit, otherwise draw random ones
+        let approximate = deal_count > limit;
+        let (player_results, iterations) = if approximate {
+            let seed = seed.unwrap_or_else(|| thread_rng().gen());
+            (
+                self.sample(&range_combos, seed, 0..limit.div_ceil(CHUNK_SIZE), limit),
+                limit,
+            )
+        } else {
+            (self.enumerate(&range_combos), deal_count)
+        };
+
+        self.get_result(player_results, iterations, approximate, start_instant)
+    }
+
+    /// Keeps sampling random runouts until every player's 95% confidence interval
+    /// on equity is narrower than `tolerance` or `time_budget` runs out. Spots with
+    /// at most `MAX_ENUMERATED_DEALS` runouts are enumerated instead.
+    pub fn get_results_until_converged(
+        &self,
+        tolerance: f64,
+        time_budget: Duration,
+        seed: Option<u64>,
+    ) -> Result {
+        let start_instant = Instant::now();
+        let range_combos = self.get_range_combos();
+
+        let deal_count = self.get_deal_count(&range_combos, MAX_ENUMERATED_DEALS);
+        if deal_count <= MAX_ENUMERATED_DEALS {
+            return self.get_result(
+                self.enumerate(&range_combos),
+                deal_count,
+                false,
+                start_instant,
+            );
+        }
+
+        let seed = seed.unwrap_or_else(|| thread_rng().gen());
+        let mut player_results = self.get_start_player_results(&range_combos);
+        let mut next_chunk = 0;
+        loop {
+            let chunks = next_chunk..next_chunk + CONVERGENCE_CHUNKS;
+            next_chunk = chunks.end;
+            player_results = sum_player_results(
+                player_results,
+                self.sample(&range_combos, seed, chunks, u64::MAX),
+            );
+
+            let result = self.get_result(
+                player_results.clone(),
+                next_chunk * CHUNK_SIZE,
+                true,
+                start_instant,
+            );
+            let converged = result
+                .player_results
+                .iter()
+                .all(|pr| pr.confidence_interval.1 - pr.confidence_interval.0 < tolerance);
+            if converged || start_instant.elapsed() >= time_budget {
+                return result;
+            }
         }
     }
 
-    fn get_unused_cards(&self, game_type: GameType) -> Vec<Card> {
-        match game_type {
-            GameType::ShortdeckHoldem => Card::get_short_deck_cards(),
-            _ => Card::get_all_cards(),
+    /// Equities after every way to deal the next street: the flop preflop, then
+    /// the turn and the river. More than `street_limit` streets are sampled at
+    /// random. The equities of each street come from `get_results` with `limit`.
+    /// Streets leaving a range seat without any combo cannot be dealt and are
+    /// only counted in `blocked`, the others are counted alike, even when they
+    /// block some combos of a range.
+    pub fn get_street_breakdown(
+        &self,
+        street_limit: u64,
+        limit: u64,
+        seed: Option<u64>,
+    ) -> std::result::Result<StreetBreakdown, Error> {
+        let board_card_count = self.community_cards.len();
+        if board_card_count >= self.game_type.board_cards() {
+            return Err(Error::NoNextStreet);
         }
-        .into_iter()
-        .filter(|c| {
-            !self.dead_cards.iter().any(|dc| *dc == *c)
-                && !self.community_cards.iter().any(|dc| *dc == *c)
-                && !self
-                    .players
-                    .iter()
-                    .any(|p| p.hand.iter().any(|hc| *hc == *c))
-        })
-        .collect()
+        let street_card_count = if board_card_count == 0 { 3 } else { 1 };
+        let seed = seed.unwrap_or_else(|| thread_rng().gen());
+        Ok(self.get_breakdown_of(street_card_count, street_limit, limit, seed))
     }
 
-    pub fn get_results(
+    /// Breakdown of every street still to come, as `get_street_breakdown` gives
+    /// the next one: preflop the flop, the flop and turn cards, then the whole
+    /// board. The average equity stays the same from street to street, the
+    /// distributions show how it spreads out until the river.
+    pub fn get_street_progression(
         &self,
-        game_type: GameType,
+        street_limit: u64,
         limit: u64,
-        trips_beat_straight: bool,
-    ) -> Result {
+        seed: Option<u64>,
+    ) -> std::result::Result<Vec<StreetBreakdown>, Error> {
+        let board_card_count = self.community_cards.len();
+        let seed = seed.unwrap_or_else(|| thread_rng().gen());
+        let first = self.get_street_breakdown(street_limit, limit, Some(seed))?;
+        let mut breakdowns = vec![];
+        for board_cards in first.board_cards + 1..=self.game_type.board_cards() {
+            breakdowns.push(self.get_breakdown_of(
+                board_cards - board_card_count,
+                street_limit,
+                limit,
+                seed,
+            ));
+        }
+        breakdowns.insert(0, first);
+        Ok(breakdowns)
+    }
+
+    fn get_breakdown_of(
+        &self,
+        street_card_count: usize,
+        street_limit: u64,
+        limit: u64,
+        seed: u64,
+    ) -> StreetBreakdown {
         let start_instant = Instant::now();
+        let unused_cards = self.get_unused_cards();
+        let approximate =
+            get_combination_count(unused_cards.len(), street_card_count) > street_limit;
+        let streets: Vec<Vec<Card>> = if approximate {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..street_limit)
+                .map(|_| {
+                    unused_cards
+                        .choose_multiple(&mut rng, street_card_count)
+                        .cloned()
+                        .collect()
+                })
+                .collect()
+        } else {
+            unused_cards
+                .into_iter()
+                .combinations(street_card_count)
+                .collect()
+        };
+
+        let mut street_results = vec![];
+        let mut blocked = 0;
+        for cards in streets {
+            match self.get_equities_with(&cards, limit, seed) {
+                Some(equities) => street_results.push(StreetResult { cards, equities }),
+                None => blocked += 1,
+            }
+        }
+
+        StreetBreakdown {
+            board_cards: self.community_cards.len() + street_card_count,
+            street_results,
+            blocked,
+            approximate,
+            time_in_ms: start_instant.elapsed().as_millis() as u64,
+        }
+    }
 
-        let start_player_results: Vec<PlayerResult> = self
-            .players
+    /// Who wins with every card left when one community card is missing. Every
+    /// card of the players has to be known.
+    pub fn get_outs(&self) -> std::result::Result<Vec<Out>, Error> {
+        self.check_missing_board_cards(1)?;
+        if self
+            .seats
             .iter()
-            .map(|p| PlayerResult {
-                hand: p.hand.clone(),
-                ties: 0,
-                wins: 0,
-                ranks: HashMap::new(),
+            .any(|seat| seat.known_cards().len() < self.game_type.hole_cards())
+        {
+            return Err(Error::UnknownCards);
+        }
+
+        let range_combos = self.get_range_combos();
+        let start_player_results = self.get_start_player_results(&range_combos);
+        let outs = self
+            .get_unused_cards()
+            .into_iter()
+            .map(|card| {
+                let mut dealt = vec![0; self.seats.len() + 1];
+                dealt[0] = card_to_mask(&card);
+                let (assignment, hand_strengths) =
+                    self.evaluate_deal(&range_combos, (vec![None; self.seats.len()], dealt));
+                let hand_combinations: Vec<HandCombination> = hand_strengths
+                    .iter()
+                    .map(|hs| hs.hand_combination)
+                    .collect();
+                let pot_shares: Vec<f64> = hand_strength_to_player_result(
+                    self.game_type,
+                    start_player_results.clone(),
+                    (assignment, hand_strengths),
+                    1.0,
+                )
+                .iter()
+                .map(|pr| pr.pot_share)
+                .collect();
+                Out {
+                    card,
+                    winners: (0..pot_shares.len())
+                        .filter(|i| pot_shares[*i] > 0.0)
+                        .collect(),
+                    pot_shares,
+                    hand_combinations,
+                }
             })
             .collect();
+        Ok(outs)
+    }
 
-        let atomic_iterations = AtomicU64::new(0);
-
-        let missing_card_count = 5 - self.community_cards.len();
-        let mut unused_cards = self.get_unused_cards(game_type);
+    /// Groups the cards left by the equities they give when two community cards
+    /// are missing, the best groups for the first player first. Equities come
+    /// from `get_results` with `limit`.
+    pub fn get_outs_by_equity(
+        &self,
+        limit: u64,
+        seed: Option<u64>,
+    ) -> std::result::Result<Vec<EquityGroup>, Error> {
+        self.check_missing_board_cards(2)?;
 
-        // Shuffle for better approximation
-        let mut rng = thread_rng();
-        unused_cards.shuffle(&mut rng);
+        let seed = seed.unwrap_or_else(|| thread_rng().gen());
+        let mut groups: Vec<EquityGroup> = vec![];
+        for card in self.get_unused_cards() {
+            let equities = match self.get_equities_with(&[card], limit, seed) {
+                Some(equities) => equities,
+                None => continue,
+            };
 
-        let hand_strength_to_player_result =
-            |results: Vec<PlayerResult>, hand_strengths: Vec<HandStrength>| -> Vec<PlayerResult> {
-                let top_points = hand_strengths
+            let same_equities = |group: &&mut EquityGroup| {
+                group
+                    .equities
                     .iter()
-                    .max_by(|a, b| a.points.cmp(&b.points))
-                    .unwrap()
-                    .points;
+                    .zip(&equities)
+                    .all(|(a, b)| (a - b).abs() < 1e-9)
+            };
+            match groups.iter_mut().find(same_equities) {
+                Some(group) => group.cards.push(card),
+                None => groups.push(EquityGroup {
+                    equities,
+                    cards: vec![card],
+                }),
+            }
+        }
+        groups.sort_by(|a, b| b.equities[0].partial_cmp(&a.equities[0]).unwrap());
+        Ok(groups)
+    }
 
-                let is_tie = hand_strengths
-                    .iter()
-                    .filter(|hs| hs.points == top_points)
-                    .take(2)
-                    .count()
-                    > 1;
+    /// Hand strength and potentials of the first player, holding all their cards,
+    /// against every holding of the second seat on a flop or later. Holdings of a
+    /// partially known hand are dealt from the cards left, a range only gives its
+    /// combos. More than `holding_limit` holdings are sampled at random. The
+    /// runouts of each holding come from `get_results` with `limit`, a
+    /// `holding_limit` of 0 compares none and gives shares of 0. Hi-lo games
+    /// compare the high hands.
+    pub fn get_hand_potential(
+        &self,
+        holding_limit: u64,
+        limit: u64,
+        seed: Option<u64>,
+    ) -> std::result::Result<HandPotential, Error> {
+        let start_instant = Instant::now();
+        if self.seats.len() != 2 {
+            return Err(Error::NotHeadsUp(self.seats.len()));
+        }
+        let hand = self.seats[0].known_cards().to_vec();
+        if hand.len() < self.game_type.hole_cards() {
+            return Err(Error::UnknownCards);
+        }
+        if self.community_cards.len() < 3 {
+            return Err(Error::NoFlop(self.community_cards.len()));
+        }
 
-                results
-                    .iter()
-                    .enumerate()
-                    .map(|(i, pr)| {
-                        let mut new_pr = pr.clone();
-                        let hand_strength = &hand_strengths[i];
-                        let rank = new_pr
-                            .ranks
-                            .entry(hand_strength.hand_combination)
-                            .or_default();
-                        *rank += 1;
-
-                        if hand_strength.points == top_points {
-                            if is_tie {
-                                new_pr.ties += 1;
-                            } else {
-                                new_pr.wins += 1;
-                            }
-                        }
-                        new_pr
-                    })
-                    .collect()
+        let seed = seed.unwrap_or_else(|| thread_rng().gen());
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (holding_count, holdings): (u64, Vec<(Vec<Card>, f64)>) = match &self.seats[1] {
+            Seat::Hand(known_cards) => {
+                let unused_cards = self.get_unused_cards();
+                let missing = self.game_type.hole_cards() - known_cards.len();
+                let holding_count = get_combination_count(unused_cards.len(), missing);
+                let get_holding = |cards: Vec<&Card>| {
+                    let holding = known_cards.iter().chain(cards).cloned().collect();
+                    (holding, 1.0)
+                };
+                let holdings = if holding_count > holding_limit {
+                    (0..holding_limit)
+                        .map(|_| {
+                            get_holding(unused_cards.choose_multiple(&mut rng, missing).collect())
+                        })
+                        .collect()
+                } else {
+                    unused_cards
+                        .iter()
+                        .combinations(missing)
+                        .map(get_holding)
+                        .collect()
+                };
+                (holding_count, holdings)
+            }
+            Seat::Range(_) => {
+                let combos = &self.get_range_combos()[1];
+                let holdings = combos
+                    .choose_multiple(&mut rng, holding_limit.min(combos.len() as u64) as usize)
+                    .map(|combo| (combo.cards.clone(), combo.weight))
+                    .collect();
+                (combos.len() as u64, holdings)
+            }
+        };
+
+        // Weights of the holdings by standing on the board, then by final standing
+        let mut standings = [0.0; 3];
+        let mut final_standings = [[0.0; 3]; 3];
+        let mut approximate = holding_count > holding_limit;
+        for (holding, weight) in &holdings {
+            let hand_strengths = game::get_results(
+                self.game_type,
+                &self.rules,
+                [cards_to_mask(&hand), cards_to_mask(holding)],
+                cards_to_mask(&self.community_cards),
+            );
+            let standing = get_standing(hand_strengths[0].points, hand_strengths[1].points);
+
+            let table = Table {
+                seats: vec![Seat::Hand(hand.clone()), Seat::Hand(holding.clone())],
+                ..self.clone()
             };
+            let result = table.get_results(limit, Some(seed));
+            approximate |= result.approximate;
+            let iterations = result.iterations as f64;
+            let ahead = result.player_results[0].wins / iterations;
+            let tied = result.player_results[0].ties / iterations;
 
-        let sum_player_results =
-            |a: Vec<PlayerResult>, b: Vec<PlayerResult>| -> Vec<PlayerResult> {
-                a.iter()
-                    .zip(b)
-                    .map(|(f, s)| {
-                        let mut new_ranks = f.ranks.clone();
-
-                        s.ranks.into_iter().for_each(|(k, v)| {
-                            let rank = new_ranks.entry(k).or_default();
-                            *rank += v;
-                        });
-
-                        PlayerResult {
-                            hand: f.hand.clone(),
-                            ties: f.ties + s.ties,
-                            wins: f.wins + s.wins,
-                            ranks: new_ranks,
-                        }
+            standings[standing] += weight;
+            final_standings[standing][AHEAD] += weight * ahead;
+            final_standings[standing][TIED] += weight * tied;
+            final_standings[standing][BEHIND] += weight * (1.0 - ahead - tied);
+        }
+
+        let total: f64 = standings.iter().sum();
+        let ratio = |a: f64, b: f64| if b > 0.0 { a / b } else { 0.0 };
+        Ok(HandPotential {
+            ahead: ratio(standings[AHEAD], total),
+            tied: ratio(standings[TIED], total),
+            behind: ratio(standings[BEHIND], total),
+            positive_potential: ratio(
+                final_standings[BEHIND][AHEAD]
+                    + final_standings[BEHIND][TIED] / 2.0
+                    + final_standings[TIED][AHEAD] / 2.0,
+                standings[BEHIND] + standings[TIED] / 2.0,
+            ),
+            negative_potential: ratio(
+                final_standings[AHEAD][BEHIND]
+                    + final_standings[AHEAD][TIED] / 2.0
+                    + final_standings[TIED][BEHIND] / 2.0,
+                standings[AHEAD] + standings[TIED] / 2.0,
+            ),
+            holdings: holding_count,
+            approximate,
+            time_in_ms: start_instant.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Equities from `get_results` with `cards` added to the community cards,
+    /// `None` when they take every combo of a range.
+    fn get_equities_with(&self, cards: &[Card], limit: u64, seed: u64) -> Option<Vec<f64>> {
+        let table = Table {
+            community_cards: self.community_cards.iter().chain(cards).cloned().collect(),
+            ..self.clone()
+        };
+        table.validate().ok()?;
+        let result = table.get_results(limit, Some(seed));
+        Some(result.player_results.iter().map(|pr| pr.equity).collect())
+    }
+
+    fn check_missing_board_cards(&self, expected: usize) -> std::result::Result<(), Error> {
+        let missing = self.game_type.board_cards() - self.community_cards.len();
+        if missing != expected {
+            return Err(Error::WrongMissingBoardCards { expected, missing });
+        }
+        Ok(())
+    }
+
+    fn validate(&self) -> std::result::Result<(), Error> {
+        self.rules.validate()?;
+        if self.seats.is_empty() {
+            return Err(Error::NoPlayers);
+        }
+        let game_type = self.game_type;
+        if self.community_cards.len() > game_type.board_cards() {
+            return Err(Error::TooManyBoardCards {
+                game_type,
+                cards: self.community_cards.len(),
+            });
+        }
+        // Missing cards of partially known or unknown hands are dealt with the board
+        if let Some((player, seat)) = self
+            .seats
+            .iter()
+            .enumerate()
+            .find(|(_, seat)| seat.known_cards().len() > game_type.hole_cards())
+        {
+            return Err(Error::WrongHandSize {
+                game_type,
+                player,
+                cards: seat.known_cards().len(),
+            });
+        }
+
+        let known_cards = self
+            .seats
+            .iter()
+            .flat_map(Seat::known_cards)
+            .chain(&self.community_cards)
+            .chain(&self.dead_cards);
+        if let Some(card) = known_cards
+            .clone()
+            .find(|c| c.rank < self.rules.lowest_rank)
+        {
+            return Err(Error::CardNotInDeck(*card));
+        }
+        if let Some(card) = known_cards.duplicates().next() {
+            return Err(Error::DuplicateCard(*card));
+        }
+
+        if game_type.hole_cards() != 2 && self.seats.iter().any(|s| matches!(s, Seat::Range(_))) {
+            return Err(Error::RangeNotSupported(game_type));
+        }
+        let range_combos = self.get_range_combos();
+        if let Some(player) = self
+            .seats
+            .iter()
+            .zip(&range_combos)
+            .position(|(seat, combos)| matches!(seat, Seat::Range(_)) && combos.is_empty())
+        {
+            return Err(Error::EmptyRange { player });
+        }
+
+        let needed = self.get_missing_card_counts().iter().sum::<usize>()
+            + range_combos
+                .iter()
+                .filter_map(|combos| combos.first())
+                .map(|combo| combo.cards.len())
+                .sum::<usize>();
+        let available = self.get_unused_cards().len();
+        if needed > available {
+            return Err(Error::DeckExhausted { needed, available });
+        }
+        if get_assignments(&range_combos).next().is_none() {
+            return Err(Error::ConflictingRanges);
+        }
+
+        Ok(())
+    }
+
+    /// Board cards are dealt first, then the missing cards of every player.
+    fn get_missing_card_counts(&self) -> Vec<usize> {
+        std::iter::once(self.game_type.board_cards() - self.community_cards.len())
+            .chain(self.seats.iter().map(|seat| match seat {
+                Seat::Hand(hand) => self.game_type.hole_cards() - hand.len(),
+                Seat::Range(_) => 0,
+            }))
+            .collect()
+    }
+
+    /// Number of deals, the valid range combo assignments times the runouts of
+    /// each. Counting the assignments is skipped when their upper bound already
+    /// exceeds `limit`, the upper bound is returned then.
+    fn get_deal_count(&self, range_combos: &[Vec<Combo>], limit: u64) -> u64 {
+        let range_card_count: usize = range_combos
+            .iter()
+            .filter_map(|combos| combos.first())
+            .map(|combo| combo.cards.len())
+            .sum();
+        let runout_count = get_deal_count(
+            self.get_unused_cards().len() - range_card_count,
+            &self.get_missing_card_counts(),
+        );
+        let max_deal_count = range_combos
+            .iter()
+            .filter(|combos| !combos.is_empty())
+            .fold(runout_count, |count, combos| {
+                count.saturating_mul(combos.len() as u64)
+            });
+
+        if range_card_count == 0 || max_deal_count > limit {
+            max_deal_count
+        } else {
+            get_assignments(range_combos).count() as u64 * runout_count
+        }
+    }
+
+    fn get_start_player_results(&self, range_combos: &[Vec<Combo>]) -> Vec<PlayerResult> {
+        self.seats
+            .iter()
+            .zip(range_combos)
+            .map(|(seat, combos)| PlayerResult {
+                hand: seat.known_cards().to_vec(),
+                ties: 0.0,
+                wins: 0.0,
+                ranks: HashMap::new(),
+                high_wins: 0.0,
+                low_wins: 0.0,
+                scoops: 0.0,
+                quartered: 0.0,
+                pot_share: 0.0,
+                pot_share_squares: 0.0,
+                equity: 0.0,
+                std_error: 0.0,
+                confidence_interval: (0.0, 0.0),
+                combo_results: combos
+                    .iter()
+                    .map(|combo| ComboResult {
+                        hand: combo.cards.clone(),
+                        weight: combo.weight,
+                        iterations: 0,
+                        deal_weight: 0.0,
+                        pot_share: 0.0,
+                        equity: 0.0,
                     })
-                    .collect()
-            };
+                    .collect(),
+            })
+            .collect()
+    }
 
-        let player_results = unused_cards
+    fn evaluate_deal(
+        &self,
+        range_combos: &[Vec<Combo>],
+        (assignment, dealt): Deal,
+    ) -> (Vec<Option<usize>>, Vec<HandStrength>) {
+        let hole_masks = self
+            .seats
             .iter()
-            .combinations(missing_card_count)
-            .take(limit as usize)
+            .zip(range_combos)
+            .zip(&assignment)
+            .zip(&dealt[1..])
+            .map(|(((seat, combos), combo), dealt)| {
+                let known_cards = match combo {
+                    Some(i) => &combos[*i].cards,
+                    None => seat.known_cards(),
+                };
+                cards_to_mask(known_cards) | dealt
+            });
+        let hand_strengths = game::get_results(
+            self.game_type,
+            &self.rules,
+            hole_masks,
+            cards_to_mask(&self.community_cards) | dealt[0],
+        );
+        (assignment, hand_strengths)
+    }
+
+    /// Evaluates every runout of every valid range combo assignment, each weighing
+    /// the product of its combo weights over their average. Like `sample` the work
+    /// is split in chunks that are summed in order, so results do not depend on how
+    /// rayon schedules them.
+    fn enumerate(&self, range_combos: &[Vec<Combo>]) -> Vec<PlayerResult> {
+        let start_player_results = self.get_start_player_results(range_combos);
+        let average_weight = get_average_assignment_weight(range_combos);
+        let unused_mask = cards_to_mask(&self.get_unused_cards());
+        let missing_card_counts = self.get_missing_card_counts();
+        let assignment_range_combos = range_combos.to_vec();
+        let mut deals = get_assignments(range_combos).flat_map(move |assignment| {
+            let assigned_mask = get_assigned_mask(&assignment_range_combos, &assignment);
+            get_deals(unused_mask & !assigned_mask, missing_card_counts.clone())
+                .map(move |deal| (assignment.clone(), deal))
+        });
+        let chunks = std::iter::from_fn(move || {
+            let chunk: Vec<Deal> = deals.by_ref().take(CHUNK_SIZE as usize).collect();
+            if chunk.is_empty() {
+                None
+            } else {
+                Some(chunk)
+            }
+        });
+
+        let mut chunk_results: Vec<(usize, Vec<PlayerResult>)> = chunks
+            .enumerate()
             .par_bridge()
-            .map(|added_cards| {
-                atomic_iterations.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-                game::get_results(
-                    game_type,
-                    trips_beat_straight,
-                    &self.players,
-                    &self
-                        .community_cards
-                        .iter()
-                        .chain(added_cards.into_iter())
-                        .cloned()
-                        .collect::<Vec<Card>>(),
+            .map(|(i, chunk)| {
+                (
+                    i,
+                    chunk
+                        .into_iter()
+                        .map(|deal| self.evaluate_deal(range_combos, deal))
+                        .fold(start_player_results.clone(), |results, deal| {
+                            let weight = match average_weight {
+                                Some(average) => {
+                                    get_assignment_weight(range_combos, &deal.0) / average
+                                }
+                                None => 1.0,
+                            };
+                            hand_strength_to_player_result(self.game_type, results, deal, weight)
+                        }),
                 )
             })
-            .fold(
-                || start_player_results.clone(),
-                hand_strength_to_player_result,
-            )
-            .reduce(|| start_player_results.clone(), sum_player_results);
+            .collect();
+        chunk_results.sort_by_key(|(i, _)| *i);
+
+        chunk_results
+            .into_iter()
+            .map(|(_, pr)| pr)
+            .fold(start_player_results, sum_player_results)
+    }
+
+    /// Draws `limit` random runouts at most, from the given chunks of `CHUNK_SIZE`.
+    /// Range combos are picked in proportion to their weights among the ones that
+    /// do not share cards.
+    fn sample(
+        &self,
+        range_combos: &[Vec<Combo>],
+        seed: u64,
+        chunks: std::ops::Range<u64>,
+        limit: u64,
+    ) -> Vec<PlayerResult> {
+        let start_player_results = self.get_start_player_results(range_combos);
+        let missing_card_counts = self.get_missing_card_counts();
+        let dealt_card_count = missing_card_counts.iter().sum();
+        let unused_cards: Vec<CardMask> =
+            self.get_unused_cards().iter().map(card_to_mask).collect();
+        let combo_distributions: Vec<Option<WeightedIndex<f64>>> = range_combos
+            .iter()
+            .map(|combos| WeightedIndex::new(combos.iter().map(|c| c.weight)).ok())
+            .collect();
+
+        let chunk_results: Vec<Vec<PlayerResult>> = chunks
+            .into_par_iter()
+            .map(|chunk| {
+                let mut rng = get_chunk_rng(seed, chunk);
+                let mut cards = unused_cards.clone();
+                (0..CHUNK_SIZE.min(limit - chunk * CHUNK_SIZE))
+                    .map(|_| {
+                        let assignment =
+                            draw_assignment(range_combos, &combo_distributions, &mut rng);
+                        // Move the combo cards behind the ones runouts are drawn from
+                        let assigned_mask = get_assigned_mask(range_combos, &assignment);
+                        let mut card_count = cards.len();
+                        let mut i = 0;
+                        while i < card_count {
+                            if cards[i] & assigned_mask != 0 {
+                                card_count -= 1;
+                                cards.swap(i, card_count);
+                            } else {
+                                i += 1;
+                            }
+                        }
+                        let (dealt, _) =
+                            cards[..card_count].partial_shuffle(&mut rng, dealt_card_count);
+                        let deal = split_deal(dealt, &missing_card_counts);
+                        self.evaluate_deal(range_combos, (assignment, deal))
+                    })
+                    .fold(start_player_results.clone(), |results, deal| {
+                        hand_strength_to_player_result(self.game_type, results, deal, 1.0)
+                    })
+            })
+            .collect();
+
+        chunk_results
+            .into_iter()
+            .fold(start_player_results, sum_player_results)
+    }
 
-        let iterations = atomic_iterations.load(std::sync::atomic::Ordering::SeqCst);
+    fn get_result(
+        &self,
+        player_results: Vec<PlayerResult>,
+        iterations: u64,
+        approximate: bool,
+        start_instant: Instant,
+    ) -> Result {
         Result {
-            player_results,
+            player_results: player_results
+                .into_iter()
+                .map(|pr| with_confidence_interval(pr, iterations, approximate))
+                .collect(),
             iterations,
-            approximate: iterations >= limit,
+            approximate,
             time_in_ms: start_instant.elapsed().as_millis() as u64,
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Adds one runout weighing `weight` to the results.
+fn hand_strength_to_player_result(
+    game_type: GameType,
+    results: Vec<PlayerResult>,
+    (assignment, hand_strengths): (Vec<Option<usize>>, Vec<HandStrength>),
+    weight: f64,
+) -> Vec<PlayerResult> {
+    let top_points = hand_strengths
+        .iter()
+        .max_by(|a, b| a.points.cmp(&b.points))
+        .unwrap()
+        .points;
 
-    #[test]
-    fn can_get_result() {
-        let table = Table::new(
-            vec![
-                Cards {
-                    cards: Card::from_cards_str("AcAd").unwrap(),
-                },
-                Cards {
-                    cards: Card::from_cards_str("AcAd").unwrap(),
-                },
-            ],
-            Card::from_cards_str("As6s5s4s3s").unwrap(),
-            vec![],
-        );
-        println!(
-            "{:#?}",
-            table.get_results(GameType::TexasHoldem, 10000, false)
-        )
-    }
+    let high_winners = hand_strengths
+        .iter()
+        .filter(|hs| hs.points == top_points)
+        .count();
+    let is_tie = high_winners > 1;
 
-    #[test]
-    fn can_get_correct_result() {
-        let table = Table::new(
-            vec![
-                Cards {
-                    cards: Card::from_cards_str("AdKc").unwrap(),
-                },
-                Cards {
-                    cards: Card::from_cards_str("Ac7c").unwrap(),
-                },
-            ],
-            Card::from_cards_str("2s3s4s5s").unwrap(),
-            vec![],
-        );
-        let result = table.get_results(GameType::TexasHoldem, 10000, false);
-        assert_eq!(
-            result.player_results[0]
+    let top_low = hand_strengths.iter().filter_map(|hs| hs.low).max();
+    let low_winners = hand_strengths
+        .iter()
+        .filter(|hs| top_low.is_some() && hs.low == top_low)
+        .count();
+
+    // Without a qualifying low the high hand takes the whole pot
+    let (high_pot, low_pot) = match top_low {
+        Some(_) => (0.5, 0.5),
+        None => (1.0, 0.0),
+    };
+
+    results
+        .into_iter()
+        .zip(&hand_strengths)
+        .zip(assignment)
+        .map(|((mut new_pr, hand_strength), combo)| {
+            let rank = new_pr
                 .ranks
-                .get(&HandCombination::Straight),
-            Some(&35u64)
-        );
+                .entry(hand_strength.hand_combination)
+                .or_default();
+            *rank += weight;
+
+            let high_winner = hand_strength.points == top_points;
+            let low_winner = top_low.is_some() && hand_strength.low == top_low;
+            let mut pot_share = 0.0;
+            if high_winner {
+                if is_tie {
+                    new_pr.ties += weight;
+                } else {
+                    new_pr.wins += weight;
+                }
+                new_pr.high_wins += weight;
+                pot_share += high_pot / high_winners as f64;
+            }
+            if low_winner {
+                new_pr.low_wins += weight;
+                pot_share += low_pot / low_winners as f64;
+            }
+
+            if game_type.is_hi_lo() {
+                let scoop = high_winner
+                    && high_winners == 1
+                    && (top_low.is_none() || (low_winner && low_winners == 1));
+                let quartered = top_low.is_some()
+                    && ((high_winner && high_winners == 2 && !low_winner)
+                        || (low_winner && low_winners == 2 && !high_winner));
+                if scoop {
+                    new_pr.scoops += weight;
+                } else if quartered {
+                    new_pr.quartered += weight;
+                }
+            }
+            new_pr.pot_share += weight * pot_share;
+            new_pr.pot_share_squares += weight * pot_share * pot_share;
+            if let Some(i) = combo {
+                new_pr.combo_results[i].iterations += 1;
+                new_pr.combo_results[i].deal_weight += weight;
+                new_pr.combo_results[i].pot_share += weight * pot_share;
+            }
+            new_pr
+        })
+        .collect()
+}
+
+fn sum_player_results(a: Vec<PlayerResult>, b: Vec<PlayerResult>) -> Vec<PlayerResult> {
+    a.iter()
+        .zip(b)
+        .map(|(f, s)| {
+            let mut new_ranks = f.ranks.clone();
+
+            s.ranks.into_iter().for_each(|(k, v)| {
+                let rank = new_ranks.entry(k).or_default();
+                *rank += v;
+            });
+
+            PlayerResult {
+                hand: f.hand.clone(),
+                ties: f.ties + s.ties,
+                wins: f.wins + s.wins,
+                ranks: new_ranks,
+                high_wins: f.high_wins + s.high_wins,
+                low_wins: f.low_wins + s.low_wins,
+                scoops: f.scoops + s.scoops,
+                quartered: f.quartered + s.quartered,
+                pot_share: f.pot_share + s.pot_share,
+                pot_share_squares: f.pot_share_squares + s.pot_share_squares,
+                equity: 0.0,
+                std_error: 0.0,
+                confidence_interval: (0.0, 0.0),
+                combo_results: f
+                    .combo_results
+                    .iter()
+                    .zip(s.combo_results)
+                    .map(|(f, s)| ComboResult {
+                        hand: f.hand.clone(),
+                        weight: f.weight,
+                        iterations: f.iterations + s.iterations,
+                        deal_weight: f.deal_weight + s.deal_weight,
+                        pot_share: f.pot_share + s.pot_share,
+                        equity: 0.0,
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Equity from the weighted pot fractions of runouts weighing `deal_weight`, 0
+/// without any.
+fn get_average_pot_share(pot_share: f64, deal_weight: f64) -> f64 {
+    if deal_weight > 0.0 {
+        pot_share / deal_weight
+    } else {
+        0.0
     }
+}
 
-    #[test]
-    fn can_get_correct_result_2() {
-        let table = Table::new(
-            vec![
-                Cards {
-                    cards: Card::from_cards_str("AdKc").unwrap(),
-                },
-                Cards {
-                    cards: Card::from_cards_str("Ac7c").unwrap(),
-                },
-            ],
-            Card::from_cards_str("2s3s4s5s6c").unwrap(),
-            vec![],
-        );
-        let result = table.get_results(GameType::TexasHoldem, 10000, false);
-        assert_eq!(result.player_results[1].wins, 1);
+/// Sets the equities. Enumerated results are exact, sampled ones get the standard
+/// error of the mean pot share and a normal approximation 95% confidence interval.
+fn with_confidence_interval(
+    player_result: PlayerResult,
+    iterations: u64,
+    approximate: bool,
+) -> PlayerResult {
+    let n = iterations as f64;
+    let equity = get_average_pot_share(player_result.pot_share, n);
+    let std_error = if approximate && iterations > 1 {
+        let variance =
+            (player_result.pot_share_squares / n - equity * equity).max(0.0) * n / (n - 1.0);
+        (variance / n).sqrt()
+    } else {
+        0.0
+    };
+
+    let combo_results = player_result
+        .combo_results
+        .into_iter()
+        .map(|cr| ComboResult {
+            equity: get_average_pot_share(cr.pot_share, cr.deal_weight),
+            ..cr
+        })
+        .collect();
+
+    PlayerResult {
+        equity,
+        std_error,
+        combo_results,
+        confidence_interval: (
+            (equity - 1.96 * std_error).max(0.0),
+            (equity + 1.96 * std_error).min(1.0),
+        ),
+        ..player_result
+    }
+}
+
+/// Standings of a player against an opponent, indexes of `get_standing`.
+const AHEAD: usize = 0;
+const TIED: usize = 1;
+const BEHIND: usize = 2;
+
+fn get_standing(points: u64, opponent_points: u64) -> usize {
+    match points.cmp(&opponent_points) {
+        std::cmp::Ordering::Greater => AHEAD,
+        std::cmp::Ordering::Equal => TIED,
+        std::cmp::Ordering::Less => BEHIND,
+    }
+}
+
+/// Number of runouts evaluated per unit of parallel work.
+const CHUNK_SIZE: u64 = 1024;
+
+/// Chunks sampled between convergence checks.
+const CONVERGENCE_CHUNKS: u64 = 16;
+
+/// Spots with at most this many runouts are enumerated when sampling until
+/// convergence.
+const MAX_ENUMERATED_DEALS: u64 = 100_000;
+
+/// Random number generator of one chunk, derived only from the seed and the chunk
+/// index so the same seed draws the same runouts on any number of threads.
+fn get_chunk_rng(seed: u64, chunk: u64) -> StdRng {
+    let mut rng_seed = [0u8; 32];
+    rng_seed[..8].copy_from_slice(&seed.to_le_bytes());
+    rng_seed[8..16].copy_from_slice(&chunk.to_le_bytes());
+    StdRng::from_seed(rng_seed)
+}
+
+fn get_combination_count(n: usize, k: usize) -> u64 {
+    if k > n {
+        return 0;
+    }
+    (0..k as u64).fold(1u64, |count, i| {
+        count.saturating_mul(n as u64 - i) / (i + 1)
+    })
+}
+
+/// Number of deals `get_deals` yields, saturating at `u64::MAX`.
+fn get_deal_count(card_count: usize, counts: &[usize]) -> u64 {
+    let mut card_count = card_count;
+    counts.iter().fold(1u64, |deal_count, count| {
+        let combinations = get_combination_count(card_count, *count);
+        card_count = card_count.saturating_sub(*count);
+        deal_count.saturating_mul(combinations)
+    })
+}
+
+/// Every way to give each range seat one of its combos without two seats sharing
+/// a card. Exact hands get `None`.
+fn get_assignments(range_combos: &[Vec<Combo>]) -> impl Iterator<Item = Vec<Option<usize>>> + Send {
+    let combo_masks: Vec<Vec<CardMask>> = range_combos
+        .iter()
+        .map(|combos| combos.iter().map(|c| cards_to_mask(&c.cards)).collect())
+        .collect();
+    get_assignments_from(Arc::new(combo_masks), 0, 0)
+}
+
+/// Assignments of the seats from `seat` on, backtracking as soon as a combo shares
+/// a card with the `used` ones so conflicting ranges are not searched through.
+fn get_assignments_from(
+    combo_masks: Arc<Vec<Vec<CardMask>>>,
+    seat: usize,
+    used: CardMask,
+) -> Box<dyn Iterator<Item = Vec<Option<usize>>> + Send> {
+    let combo_count = match combo_masks.get(seat) {
+        Some(masks) => masks.len(),
+        None => return Box::new(std::iter::once(vec![])),
+    };
+
+    if combo_count == 0 {
+        return Box::new(get_assignments_from(combo_masks, seat + 1, used).map(
+            |mut assignment| {
+                assignment.insert(0, None);
+                assignment
+            },
+        ));
+    }
+
+    let seat_masks = combo_masks.clone();
+    Box::new(
+        (0..combo_count)
+            .filter(move |i| seat_masks[seat][*i] & used == 0)
+            .flat_map(move |i| {
+                let mask = combo_masks[seat][i];
+                get_assignments_from(combo_masks.clone(), seat + 1, used | mask).map(
+                    move |mut assignment| {
+                        assignment.insert(0, Some(i));
+                        assignment
+                    },
+                )
+            }),
+    )
+}
+
+/// Draws range combos until no two seats share a card, which picks the valid
+/// assignments in proportion to the product of their combo weights.
+fn draw_assignment(
+    range_combos: &[Vec<Combo>],
+    combo_distributions: &[Option<WeightedIndex<f64>>],
+    rng: &mut StdRng,
+) -> Vec<Option<usize>> {
+    loop {
+        let assignment: Vec<Option<usize>> = combo_distributions
+            .iter()
+            .map(|distribution| distribution.as_ref().map(|d| d.sample(&mut *rng)))
+            .collect();
+        if !has_conflict(range_combos, &assignment) {
+            return assignment;
+        }
+    }
+}
+
+/// Average weight of the valid range combo assignments, `None` when every range
+/// gives its combos the same weight so all assignments weigh the same.
+fn get_average_assignment_weight(range_combos: &[Vec<Combo>]) -> Option<f64> {
+    if range_combos
+        .iter()
+        .all(|combos| combos.iter().all(|c| c.weight == combos[0].weight))
+    {
+        return None;
+    }
+    let (count, total) = get_assignments(range_combos).fold((0, 0.0), |(count, total), a| {
+        (count + 1, total + get_assignment_weight(range_combos, &a))
+    });
+    Some(total / count as f64)
+}
+
+/// Product of the weights of the assigned combos, 1 without range seats.
+fn get_assignment_weight(range_combos: &[Vec<Combo>], assignment: &[Option<usize>]) -> f64 {
+    range_combos
+        .iter()
+        .zip(assignment)
+        .filter_map(|(combos, combo)| combo.map(|i| combos[i].weight))
+        .product()
+}
+
+fn get_assigned_mask(range_combos: &[Vec<Combo>], assignment: &[Option<usize>]) -> CardMask {
+    range_combos
+        .iter()
+        .zip(assignment)
+        .filter_map(|(combos, combo)| combo.map(|i| cards_to_mask(&combos[i].cards)))
+        .fold(0, |mask, combo_mask| mask | combo_mask)
+}
+
+fn has_conflict(range_combos: &[Vec<Combo>], assignment: &[Option<usize>]) -> bool {
+    let mut used = 0;
+    range_combos
+        .iter()
+        .zip(assignment)
+        .filter_map(|(combos, combo)| combo.map(|i| cards_to_mask(&combos[i].cards)))
+        .any(|mask| {
+            let conflict = mask & used != 0;
+            used |= mask;
+            conflict
+        })
+}
+
+/// Splits randomly drawn cards into the board and player cards of one deal.
+fn split_deal(cards: &[CardMask], counts: &[usize]) -> Vec<CardMask> {
+    let mut start = 0;
+    counts
+        .iter()
+        .map(|count| {
+            start += count;
+            cards[start - count..start]
+                .iter()
+                .fold(0, |mask, card| mask | card)
+        })
+        .collect()
+}
+
+/// Every way to deal `counts[0]` cards from `cards`, then `counts[1]` cards from
+/// the ones left and so on, as the mask of the cards of each count.
+fn get_deals(
+    cards: CardMask,
+    counts: Vec<usize>,
+) -> Box<dyn Iterator<Item = Vec<CardMask>> + Send> {
+    let (count, rest) = match counts.split_first() {
+        Some((count, rest)) => (*count, rest.to_vec()),
+        None => return Box::new(std::iter::once(vec![])),
+    };
+
+    if rest.iter().all(|c| *c == 0) {
+        return Box::new(get_card_combinations(cards, count).map(move |dealt| {
+            std::iter::once(dealt)
+                .chain(rest.iter().map(|_| 0))
+                .collect()
+        }));
+    }
+
+    Box::new(get_card_combinations(cards, count).flat_map(move |dealt| {
+        get_deals(cards & !dealt, rest.clone()).map(move |mut deal| {
+            deal.insert(0, dealt);
+            deal
+        })
+    }))
+}
+
+/// Masks of every `count` cards of `cards`. The positions of the chosen cards
+/// are the bits of an index going through its values with `count` bits set in
+/// increasing order (Gosper's hack).
+fn get_card_combinations(cards: CardMask, count: usize) -> impl Iterator<Item = CardMask> + Send {
+    let card_masks: Vec<CardMask> = get_card_masks(cards).collect();
+    let end = 1u64 << card_masks.len();
+    let mut next = Some((1u64 << count) - 1).filter(|positions| *positions < end);
+    std::iter::from_fn(move || {
+        let positions = next?;
+        next = if positions == 0 {
+            None
+        } else {
+            let lowest = positions & positions.wrapping_neg();
+            let ripple = positions + lowest;
+            Some((((ripple ^ positions) >> 2) / lowest) | ripple).filter(|next| *next < end)
+        };
+        Some(
+            get_card_masks(positions)
+                .map(|position| card_masks[position.trailing_zeros() as usize])
+                .fold(0, |mask, card| mask | card),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{card::Rank, Ante};
+
+    #[test]
+    fn can_get_result() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::Hand(Card::from_cards_str("AcAd").unwrap()),
+                Seat::Hand(Card::from_cards_str("KhKd").unwrap()),
+            ],
+            Card::from_cards_str("As6s5s4s3s").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        println!("{:#?}", table.get_results(10000, None))
+    }
+
+    #[test]
+    fn can_get_correct_result() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::Hand(Card::from_cards_str("AdKc").unwrap()),
+                Seat::Hand(Card::from_cards_str("Ac7c").unwrap()),
+            ],
+            Card::from_cards_str("2s3s4s5s").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(10000, None);
+        assert_eq!(
+            result.player_results[0]
+                .ranks
+                .get(&HandCombination::Straight),
+            Some(&35.0)
+        );
+    }
+
+    #[test]
+    fn can_get_correct_result_2() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::Hand(Card::from_cards_str("AdKc").unwrap()),
+                Seat::Hand(Card::from_cards_str("Ac7c").unwrap()),
+            ],
+            Card::from_cards_str("2s3s4s5s6c").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(10000, None);
+        assert_eq!(result.player_results[1].wins, 1.0);
+        assert_eq!(result.player_results[1].scoops, 0.0);
+    }
+
+    #[test]
+    fn can_split_pot_between_tied_players() {
+        let hands = ["2c3c", "2d3d", "2h3h", "4c4d", "5c5d"];
+        let table = Table::new(
+            GameType::TexasHoldem,
+            hands
+                .iter()
+                .map(|hand| Seat::from_str(hand).unwrap())
+                .collect(),
+            Card::from_cards_str("AsKsQsJsTs").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(1, None);
+
+        for pr in &result.player_results {
+            assert_eq!(pr.ties, 1.0);
+            assert!((pr.equity - 0.2).abs() < 1e-9);
+        }
+
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("Tc9c").unwrap(),
+                Seat::from_str("Td8d").unwrap(),
+                Seat::from_str("3c4c").unwrap(),
+            ],
+            Card::from_cards_str("AhKhQdJc2s").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let equities: Vec<f64> = table
+            .get_results(1, None)
+            .player_results
+            .iter()
+            .map(|pr| pr.equity)
+            .collect();
+
+        for (equity, expected) in equities.iter().zip(&[0.5, 0.5, 0.0]) {
+            assert!((equity - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn can_get_equity_with_ties() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AcKd").unwrap(),
+                Seat::from_str("AdKc").unwrap(),
+                Seat::from_str("7h7s").unwrap(),
+            ],
+            Card::from_cards_str("KhQh2c").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(1000, None);
+        let player_results = &result.player_results;
+
+        assert!(!result.approximate);
+        assert!((player_results.iter().map(|pr| pr.equity).sum::<f64>() - 1.0).abs() < 1e-9);
+        // The ace king hands chop unless the sevens hit a set
+        let seven_wins = player_results[2].wins;
+        assert!(player_results[0].ties > 0.0);
+        assert_eq!(player_results[0].wins, 0.0);
+        assert!(
+            (player_results[0].equity
+                - (result.iterations as f64 - seven_wins) / 2.0 / result.iterations as f64)
+                .abs()
+                < 1e-9
+        );
+    }
+
+    #[test]
+    fn can_get_omaha5_result() {
+        let table = Table::new(
+            GameType::Omaha5,
+            vec![
+                Seat::Hand(Card::from_cards_str("AhKh2c3d9s").unwrap()),
+                Seat::Hand(Card::from_cards_str("AdKd2h3c9c").unwrap()),
+            ],
+            Card::from_cards_str("QhJh9h4s").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(10000, None);
+        assert_eq!(result.iterations, 38);
+        assert!(result.player_results[0].wins > result.player_results[1].wins);
+    }
+
+    #[test]
+    fn cannot_create_table_with_wrong_hand_size() {
+        let table = Table::new(
+            GameType::Omaha6,
+            vec![
+                Seat::Hand(Card::from_cards_str("AhKh2c3d9s8s").unwrap()),
+                Seat::Hand(Card::from_cards_str("AdKd2h3c9c8c7c").unwrap()),
+            ],
+            vec![],
+            vec![],
+        );
+        assert_eq!(
+            table.unwrap_err(),
+            Error::WrongHandSize {
+                game_type: GameType::Omaha6,
+                player: 1,
+                cards: 7
+            }
+        );
+    }
+
+    #[test]
+    fn cannot_create_table_with_duplicate_cards() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::Hand(Card::from_cards_str("AcAd").unwrap()),
+                Seat::Hand(Card::from_cards_str("KhKd").unwrap()),
+            ],
+            Card::from_cards_str("2s3s4s").unwrap(),
+            Card::from_cards_str("Kd").unwrap(),
+        );
+        assert_eq!(
+            table.unwrap_err(),
+            Error::DuplicateCard(Card::from_str("Kd").unwrap())
+        );
+    }
+
+    #[test]
+    fn cannot_create_table_with_cards_not_in_deck() {
+        let table = Table::new(
+            GameType::ShortdeckHoldem,
+            vec![
+                Seat::Hand(Card::from_cards_str("AcAd").unwrap()),
+                Seat::Hand(Card::from_cards_str("KhKd").unwrap()),
+            ],
+            Card::from_cards_str("6s7s2c").unwrap(),
+            vec![],
+        );
+        assert_eq!(
+            table.unwrap_err(),
+            Error::CardNotInDeck(Card::from_str("2c").unwrap())
+        );
+
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![Seat::Hand(Card::from_cards_str("5c5d").unwrap())],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(
+            table
+                .with_rules(Rules::short_deck(Ante::AnteOnly))
+                .unwrap_err(),
+            Error::CardNotInDeck(Card::from_str("5c").unwrap())
+        );
+    }
+
+    #[test]
+    fn cannot_create_table_with_too_many_board_cards() {
+        let table = Table::new(
+            GameType::SevenCardStud,
+            vec![Seat::Hand(Card::from_cards_str("AcAd").unwrap())],
+            Card::from_cards_str("2s").unwrap(),
+            vec![],
+        );
+        assert_eq!(
+            table.unwrap_err(),
+            Error::TooManyBoardCards {
+                game_type: GameType::SevenCardStud,
+                cards: 1
+            }
+        );
+    }
+
+    #[test]
+    fn cannot_create_table_when_deck_is_exhausted() {
+        let table = Table::new(
+            GameType::SevenCardStud,
+            (0..8).map(|_| Seat::Hand(vec![])).collect(),
+            vec![],
+            vec![],
+        );
+        assert_eq!(
+            table.unwrap_err(),
+            Error::DeckExhausted {
+                needed: 56,
+                available: 52
+            }
+        );
+    }
+
+    #[test]
+    fn can_get_omaha_hi_lo_result() {
+        let table = Table::new(
+            GameType::OmahaHiLo,
+            vec![
+                Seat::Hand(Card::from_cards_str("AhKh2c3d").unwrap()),
+                Seat::Hand(Card::from_cards_str("KdKcQsQd").unwrap()),
+            ],
+            Card::from_cards_str("Ks4h5d8c9s").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(10000, None);
+        let hi_lo_player = &result.player_results[0];
+        let high_player = &result.player_results[1];
+
+        assert_eq!(hi_lo_player.low_wins, 1.0);
+        assert_eq!(hi_lo_player.high_wins, 0.0);
+        assert!((hi_lo_player.equity - 0.5).abs() < 1e-9);
+        assert_eq!(high_player.high_wins, 1.0);
+        assert!((high_player.equity - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn can_get_omaha_hi_lo_scoop_and_quarter() {
+        let table = Table::new(
+            GameType::OmahaHiLo,
+            vec![
+                Seat::Hand(Card::from_cards_str("Ah2hKdKc").unwrap()),
+                Seat::Hand(Card::from_cards_str("As2sQhQd").unwrap()),
+            ],
+            Card::from_cards_str("3c4c5d9sJd").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(10000, None);
+
+        for pr in &result.player_results {
+            assert_eq!(pr.high_wins, 1.0);
+            assert_eq!(pr.low_wins, 1.0);
+            assert!((pr.equity - 0.5).abs() < 1e-9);
+        }
+
+        let table = Table::new(
+            GameType::OmahaHiLo,
+            vec![
+                Seat::Hand(Card::from_cards_str("Ah2hKdKc").unwrap()),
+                Seat::Hand(Card::from_cards_str("As2sQhQd").unwrap()),
+                Seat::Hand(Card::from_cards_str("JhJcTs9d").unwrap()),
+            ],
+            Card::from_cards_str("3c4c8dJs7d").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(10000, None);
+
+        assert_eq!(result.player_results[0].quartered, 1.0);
+        assert_eq!(result.player_results[1].quartered, 1.0);
+        assert_eq!(result.player_results[2].scoops, 0.0);
+        assert!((result.player_results[2].equity - 0.5).abs() < 1e-9);
+
+        // Without a qualifying low the best high hand scoops
+        let table = Table::new(
+            GameType::OmahaHiLo,
+            vec![
+                Seat::Hand(Card::from_cards_str("AhAcQd2d").unwrap()),
+                Seat::Hand(Card::from_cards_str("3c4c5d6d").unwrap()),
+            ],
+            Card::from_cards_str("KcKdJs9h8h").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(10000, None);
+
+        assert_eq!(result.player_results[0].scoops, 1.0);
+        assert_eq!(result.player_results[1].quartered, 0.0);
+    }
+
+    #[test]
+    fn can_get_deals() {
+        let cards = cards_to_mask(&Card::from_cards_str("AcKcQc").unwrap());
+        let deals: Vec<Vec<CardMask>> = get_deals(cards, vec![1, 0, 2]).collect();
+
+        assert_eq!(deals.len(), 3);
+        for deal in &deals {
+            assert_eq!(deal[0].count_ones(), 1);
+            assert_eq!(deal[1], 0);
+            assert_eq!(deal[2].count_ones(), 2);
+            assert_eq!(deal[0] | deal[2], cards);
+        }
+        assert!(deals.iter().map(|deal| deal[0]).all_unique());
+        assert_eq!(get_deals(cards, vec![0, 3]).count(), 1);
+        assert_eq!(get_deals(cards, vec![4]).count(), 0);
+    }
+
+    #[test]
+    fn can_get_card_combinations() {
+        let cards = cards_to_mask(&Card::get_all_cards()[..10]);
+        let combinations: Vec<CardMask> = get_card_combinations(cards, 3).collect();
+
+        assert_eq!(combinations.len(), 120);
+        assert!(combinations.iter().all_unique());
+        assert!(combinations
+            .iter()
+            .all(|mask| mask.count_ones() == 3 && mask & !cards == 0));
+    }
+
+    #[test]
+    fn can_get_stud_result() {
+        let table = Table::new(
+            GameType::SevenCardStud,
+            vec![
+                Seat::Hand(Card::from_cards_str("AhAdAcKs2c3c").unwrap()),
+                Seat::Hand(Card::from_cards_str("7h8h9hThJh5d").unwrap()),
+            ],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(10000, None);
+
+        assert_eq!(result.iterations, 40 * 39);
+        assert_eq!(result.player_results[1].wins, (40 * 39) as f64);
+    }
+
+    #[test]
+    fn can_get_razz_result() {
+        let table = Table::new(
+            GameType::Razz,
+            vec![
+                Seat::Hand(Card::from_cards_str("Ah2d3c4s5h").unwrap()),
+                Seat::Hand(Card::from_cards_str("KhKdKcKsQh").unwrap()),
+            ],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(1000, None);
+
+        assert_eq!(result.player_results[0].wins, result.iterations as f64);
+    }
+
+    #[test]
+    fn can_get_stud_hi_lo_result() {
+        let table = Table::new(
+            GameType::SevenCardStudHiLo,
+            vec![
+                Seat::Hand(Card::from_cards_str("Ah2d3c4s5hKh").unwrap()),
+                Seat::Hand(Card::from_cards_str("QhQdQcJsJhTd").unwrap()),
+            ],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(10000, None);
+
+        assert_eq!(result.player_results[0].low_wins, result.iterations as f64);
+        assert_eq!(result.player_results[1].low_wins, 0.0);
+    }
+
+    #[test]
+    fn can_get_deal_count() {
+        assert_eq!(get_deal_count(48, &[5, 0, 0]), 1_712_304);
+        assert_eq!(get_deal_count(40, &[0, 1, 1]), 40 * 39);
+        assert_eq!(get_deal_count(3, &[2, 2]), 0);
+        assert_eq!(get_deal_count(52, &[5, 7, 7, 7, 7, 7]), u64::MAX);
+    }
+
+    #[test]
+    fn can_enumerate_when_within_limit() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::Hand(Card::from_cards_str("AdKc").unwrap()),
+                Seat::Hand(Card::from_cards_str("Ac7c").unwrap()),
+            ],
+            Card::from_cards_str("2s3s4s").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(990, None);
+
+        assert!(!result.approximate);
+        assert_eq!(result.iterations, 990);
+    }
+
+    #[test]
+    fn can_sample_random_runouts() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::Hand(Card::from_cards_str("AhAd").unwrap()),
+                Seat::Hand(Card::from_cards_str("KhKd").unwrap()),
+            ],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(5000, None);
+        let equity = result.player_results[0].equity;
+
+        assert!(result.approximate);
+        assert_eq!(result.iterations, 5000);
+        // AhAd vs KhKd is about 82% to 18%
+        assert!((equity - 0.82).abs() < 0.04, "equity {}", equity);
+
+        let (low, high) = result.player_results[0].confidence_interval;
+        assert!(result.player_results[0].std_error > 0.0);
+        assert!(low < equity && equity < high);
+        assert!(high - low < 0.04);
+    }
+
+    #[test]
+    fn can_get_same_result_with_same_seed() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::Hand(Card::from_cards_str("AhAd").unwrap()),
+                Seat::Hand(Card::from_cards_str("7c8c").unwrap()),
+                Seat::Hand(Card::from_cards_str("KsQs").unwrap()),
+            ],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let get_results = |threads: usize, seed: u64| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| table.get_results(5000, Some(seed)))
+        };
+
+        let result = get_results(1, 42);
+        for other in [get_results(4, 42), get_results(3, 42)] {
+            assert_eq!(other.iterations, result.iterations);
+            for (pr, other_pr) in result.player_results.iter().zip(&other.player_results) {
+                assert_eq!(pr.wins, other_pr.wins);
+                assert_eq!(pr.ties, other_pr.ties);
+                assert_eq!(pr.ranks, other_pr.ranks);
+                assert_eq!(pr.pot_share.to_bits(), other_pr.pot_share.to_bits());
+            }
+        }
+
+        let other = get_results(4, 43);
+        assert!(result
+            .player_results
+            .iter()
+            .zip(&other.player_results)
+            .any(|(pr, other_pr)| pr.wins != other_pr.wins));
+    }
+
+    #[test]
+    fn can_get_exact_confidence_interval_when_enumerated() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::Hand(Card::from_cards_str("AdKc").unwrap()),
+                Seat::Hand(Card::from_cards_str("Ac7c").unwrap()),
+            ],
+            Card::from_cards_str("2s3s4s5s").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results_until_converged(0.001, Duration::from_secs(60), None);
+        let pr = &result.player_results[1];
+
+        assert!(!result.approximate);
+        assert_eq!(result.iterations, 44);
+        assert_eq!(pr.std_error, 0.0);
+        assert_eq!(pr.confidence_interval.0, pr.confidence_interval.1);
+    }
+
+    #[test]
+    fn can_sample_until_converged() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::Hand(Card::from_cards_str("AhAd").unwrap()),
+                Seat::Hand(Card::from_cards_str("KhKd").unwrap()),
+            ],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results_until_converged(0.05, Duration::from_secs(60), Some(1));
+
+        assert!(result.approximate);
+        assert_eq!(result.iterations, CONVERGENCE_CHUNKS * CHUNK_SIZE);
+        for pr in &result.player_results {
+            assert!(pr.confidence_interval.1 - pr.confidence_interval.0 < 0.05);
+        }
+    }
+
+    #[test]
+    fn can_stop_sampling_when_out_of_time() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::Hand(Card::from_cards_str("AhAd").unwrap()),
+                Seat::Hand(Card::from_cards_str("KhKd").unwrap()),
+            ],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results_until_converged(0.0, Duration::from_millis(1), Some(1));
+
+        assert!(result.approximate);
+        assert_eq!(result.iterations, CONVERGENCE_CHUNKS * CHUNK_SIZE);
+    }
+
+    #[test]
+    fn can_get_result_against_unknown_hands() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AsAh").unwrap(),
+                Seat::Hand(vec![]),
+                Seat::Hand(vec![]),
+                Seat::Hand(vec![]),
+            ],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(20000, Some(2));
+        let hero = &result.player_results[0];
+
+        // Aces against three random hands
+        assert!(result.approximate);
+        assert!((hero.equity - 0.64).abs() < 0.02);
+        assert!(result.player_results[1].hand.is_empty());
+    }
+
+    #[test]
+    fn can_get_result_with_partially_known_hand() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhKh").unwrap(),
+                Seat::from_str("Qc").unwrap(),
+            ],
+            Card::from_cards_str("2c7d9hJsQs").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(1000, None);
+
+        // The queen pairs the board whatever the second card is
+        assert!(!result.approximate);
+        assert_eq!(result.iterations, 44);
+        assert_eq!(result.player_results[1].wins, 44.0);
+    }
+
+    #[test]
+    fn can_get_omaha_result_with_partially_known_hand() {
+        let table = Table::new(
+            GameType::Omaha,
+            vec![
+                Seat::from_str("AhAdKhKd").unwrap(),
+                Seat::from_str("QsJs").unwrap(),
+            ],
+            Card::from_cards_str("2c7d9hTs").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(100000, None);
+
+        assert!(!result.approximate);
+        assert_eq!(result.iterations, 42 * 820);
+    }
+
+    #[test]
+    fn can_get_short_deck_result_with_rules() {
+        let table = Table::new(
+            GameType::ShortdeckHoldem,
+            vec![
+                Seat::from_str("AhQd").unwrap(),
+                Seat::from_str("9hTd").unwrap(),
+            ],
+            Card::from_cards_str("QsQc6d7c8s").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let straight_wins = table.get_results(1, None);
+        let trips_win = table
+            .with_rules(Rules::short_deck(Ante::AnteOnly))
+            .unwrap()
+            .get_results(1, None);
+
+        assert_eq!(straight_wins.player_results[1].wins, 1.0);
+        assert_eq!(trips_win.player_results[0].wins, 1.0);
+    }
+
+    #[test]
+    fn cannot_play_with_rules_of_smaller_deck() {
+        let seats = vec![Seat::Hand(vec![]); 17];
+        let table = Table::new(GameType::TexasHoldem, seats, vec![], vec![]).unwrap();
+
+        assert_eq!(
+            table
+                .with_rules(Rules::short_deck(Ante::ButtonBlind))
+                .unwrap_err(),
+            Error::DeckExhausted {
+                needed: 39,
+                available: 36
+            }
+        );
+    }
+
+    #[test]
+    fn cannot_play_with_incomplete_ranking() {
+        let seats = vec![Seat::Hand(vec![]); 2];
+        let table = Table::new(GameType::TexasHoldem, seats, vec![], vec![]).unwrap();
+        let mut rules = Rules::new(GameType::TexasHoldem);
+        rules.ranking[9] = HandCombination::OnePair;
+
+        assert_eq!(table.with_rules(rules).unwrap_err(), Error::InvalidRanking);
+    }
+
+    #[test]
+    fn can_parse_seat() {
+        assert_eq!(
+            Seat::from_str("AcKh"),
+            Ok(Seat::Hand(Card::from_cards_str("AcKh").unwrap()))
+        );
+        assert_eq!(
+            Seat::from_str("TT+,AKs"),
+            Ok(Seat::Range(Range::from_str("TT+,AKs").unwrap()))
+        );
+        assert!(Seat::from_str("AKx").is_err());
+        assert_eq!(
+            Seat::from_str("AcKx"),
+            Err(Error::UnknownSuit {
+                suit: "x".to_string(),
+                position: 3
+            })
+        );
+    }
+
+    #[test]
+    fn can_get_hand_vs_range_result() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhKh").unwrap(),
+                Seat::from_str("TT, AK").unwrap(),
+            ],
+            Card::from_cards_str("2c7d9hJsQc").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(1000, None);
+        let hero = &result.player_results[0];
+        let villain = &result.player_results[1];
+
+        // 6 combos of tens win, the 9 AK combos left chop
+        assert!(!result.approximate);
+        assert_eq!(result.iterations, 15);
+        assert!(hero.combo_results.is_empty());
+        assert!((hero.equity - 0.3).abs() < 1e-9);
+        assert!(villain.hand.is_empty());
+        assert_eq!(villain.combo_results.len(), 15);
+        for cr in &villain.combo_results {
+            assert_eq!(cr.iterations, 1);
+            let expected = if cr.hand[0].rank == Rank::Ten {
+                1.0
+            } else {
+                0.5
+            };
+            assert!((cr.equity - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn can_get_range_vs_range_result() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![Seat::from_str("AA").unwrap(), Seat::from_str("AA").unwrap()],
+            Card::from_cards_str("2c7d9hJsQc").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(1000, None);
+
+        // Every pair of aces leaves exactly one for the other seat
+        assert_eq!(result.iterations, 6);
+        for pr in &result.player_results {
+            assert_eq!(pr.ties, 6.0);
+            assert!(pr.combo_results.iter().all(|cr| cr.iterations == 1));
+        }
+    }
+
+    #[test]
+    fn can_remove_range_combos_using_known_cards() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhKd").unwrap(),
+                Seat::from_str("AA").unwrap(),
+            ],
+            Card::from_cards_str("As2c3c").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(100000, None);
+        let combo_results = &result.player_results[1].combo_results;
+
+        assert_eq!(combo_results.len(), 1);
+        assert_eq!(combo_results[0].hand, Card::from_cards_str("AcAd").unwrap());
+        assert_eq!(result.iterations, 990);
+    }
+
+    #[test]
+    fn can_sample_weighted_range() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhKh").unwrap(),
+                Seat::from_str("TT, AK:0.5").unwrap(),
+            ],
+            Card::from_cards_str("2c7d9h").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(10000, Some(5));
+        let villain = &result.player_results[1];
+        let ten_iterations: u64 = villain
+            .combo_results
+            .iter()
+            .filter(|cr| cr.hand[0].rank == Rank::Ten)
+            .map(|cr| cr.iterations)
+            .sum();
+
+        // Tens weigh 6, the 9 AK combos left 4.5
+        assert!(result.approximate);
+        assert!((ten_iterations as f64 / result.iterations as f64 - 6.0 / 10.5).abs() < 0.02);
+        let exact = table.get_results(20000, None);
+        assert!(!exact.approximate);
+        assert!((result.player_results[0].equity - exact.player_results[0].equity).abs() < 0.02);
+    }
+
+    #[test]
+    fn can_enumerate_weighted_range() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhKh").unwrap(),
+                Seat::from_str("TT, AK:0.5").unwrap(),
+            ],
+            Card::from_cards_str("2c7d9hJsQc").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(1000, None);
+
+        // Tens weigh 6, the 9 AK combos left 4.5 and chop
+        assert!(!result.approximate);
+        assert_eq!(result.iterations, 15);
+        assert!((result.player_results[0].equity - 2.25 / 10.5).abs() < 1e-9);
+        assert_eq!(result.player_results[0].std_error, 0.0);
+
+        // Every combo is dealt the same runouts, so the equity is the average of the
+        // equities against each combo weighted by the combo weights
+        let board = Card::from_cards_str("2c7d9h").unwrap();
+        let range = Range::from_str("QQ+,AKs:0.5").unwrap();
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![Seat::from_str("AhKh").unwrap(), Seat::Range(range.clone())],
+            board.clone(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(100000, None);
+        assert!(!result.approximate);
+
+        let combos = &table.get_range_combos()[1];
+        let weighted_equity: f64 = combos
+            .iter()
+            .map(|combo| {
+                let table = Table::new(
+                    GameType::TexasHoldem,
+                    vec![
+                        Seat::from_str("AhKh").unwrap(),
+                        Seat::Hand(combo.cards.clone()),
+                    ],
+                    board.clone(),
+                    vec![],
+                )
+                .unwrap();
+                combo.weight * table.get_results(1000, None).player_results[0].equity
+            })
+            .sum();
+        let weight: f64 = combos.iter().map(|combo| combo.weight).sum();
+        assert!((result.player_results[0].equity - weighted_equity / weight).abs() < 1e-9);
+    }
+
+    #[test]
+    fn can_enumerate_range_with_equal_weights() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhKh").unwrap(),
+                Seat::from_str("TT:0.5, AK:0.5").unwrap(),
+            ],
+            Card::from_cards_str("2c7d9hJsQc").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(1000, None);
+
+        assert!(!result.approximate);
+        assert_eq!(result.iterations, 15);
+    }
+
+    #[test]
+    fn can_sample_hand_vs_range() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AsAh").unwrap(),
+                Seat::from_str("KK").unwrap(),
+            ],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let result = table.get_results(20000, Some(3));
+        let hero = &result.player_results[0];
+        let villain = &result.player_results[1];
+
+        assert!(result.approximate);
+        assert!((hero.equity - 0.82).abs() < 0.02);
+        assert_eq!(villain.combo_results.len(), 6);
+        assert_eq!(
+            villain
+                .combo_results
+                .iter()
+                .map(|cr| cr.iterations)
+                .sum::<u64>(),
+            result.iterations
+        );
+        for cr in &villain.combo_results {
+            assert!((cr.equity - 0.18).abs() < 0.03);
+        }
+    }
+
+    #[test]
+    fn cannot_use_ranges_in_omaha() {
+        let table = Table::new(
+            GameType::Omaha,
+            vec![
+                Seat::from_str("AhAdKhKd").unwrap(),
+                Seat::from_str("QQ").unwrap(),
+            ],
+            vec![],
+            vec![],
+        );
+        assert_eq!(
+            table.unwrap_err(),
+            Error::RangeNotSupported(GameType::Omaha)
+        );
+    }
+
+    #[test]
+    fn cannot_use_ranges_without_combos_left() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhAd").unwrap(),
+                Seat::from_str("AA").unwrap(),
+            ],
+            vec![],
+            Card::from_cards_str("Ac").unwrap(),
+        );
+        assert_eq!(table.unwrap_err(), Error::EmptyRange { player: 1 });
+
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhKh").unwrap(),
+                Seat::from_str("AhKh").unwrap(),
+            ],
+            vec![],
+            vec![],
+        );
+        assert_eq!(
+            table.unwrap_err(),
+            Error::DuplicateCard(Card::from_str("Ah").unwrap())
+        );
+
+        // Two disjoint combos for three players
+        let table = Table::new(
+            GameType::TexasHoldem,
+            (0..3)
+                .map(|_| Seat::from_str("AhKh, AsKs").unwrap())
+                .collect(),
+            vec![],
+            vec![],
+        );
+        assert_eq!(table.unwrap_err(), Error::ConflictingRanges);
+
+        // Found without walking the 12^9 combinations of the ranges
+        let table = Table::new(
+            GameType::TexasHoldem,
+            (0..9).map(|_| Seat::from_str("AA,KK").unwrap()).collect(),
+            vec![],
+            vec![],
+        );
+        assert_eq!(table.unwrap_err(), Error::ConflictingRanges);
+
+        let table = Table::new(GameType::TexasHoldem, vec![], vec![], vec![]);
+        assert_eq!(table.unwrap_err(), Error::NoPlayers);
+    }
+
+    #[test]
+    fn can_get_turn_breakdown() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhAd").unwrap(),
+                Seat::from_str("KhKd").unwrap(),
+            ],
+            Card::from_cards_str("2c7s9d").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let breakdown = table.get_street_breakdown(100, 1000, None).unwrap();
+
+        assert!(!breakdown.approximate);
+        assert_eq!(breakdown.street_results.len(), 45);
+        // Every turn is as likely, so on average they give the flop equity
+        let equity = table.get_results(1000, None).player_results[0].equity;
+        assert!((breakdown.get_average_equity(0).unwrap() - equity).abs() < 1e-9);
+
+        let kings_turn = breakdown
+            .street_results
+            .iter()
+            .find(|sr| sr.cards == Card::from_cards_str("Kc").unwrap())
+            .unwrap();
+        assert_eq!(kings_turn.equities, vec![2.0 / 44.0, 42.0 / 44.0]);
+        let histogram = breakdown.get_equity_histogram(1, 10);
+        assert_eq!(histogram.iter().sum::<u64>(), 45);
+        assert_eq!(histogram[0], 43);
+        assert_eq!(histogram[9], 2);
+        assert!(breakdown.get_equity_histogram(1, 0).is_empty());
+    }
+
+    #[test]
+    fn can_get_street_progression() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhAd").unwrap(),
+                Seat::from_str("KhKd").unwrap(),
+            ],
+            Card::from_cards_str("2c7s9d").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let progression = table.get_street_progression(1000, 1000, None).unwrap();
+
+        assert_eq!(progression.len(), 2);
+        assert_eq!(progression[0].board_cards, 4);
+        assert_eq!(progression[0].street_results.len(), 45);
+        assert_eq!(progression[1].board_cards, 5);
+        assert_eq!(progression[1].street_results.len(), 45 * 44 / 2);
+        let equity = table.get_results(1000, None).player_results[0].equity;
+        for breakdown in &progression {
+            assert!(!breakdown.approximate);
+            assert!((breakdown.get_average_equity(0).unwrap() - equity).abs() < 1e-9);
+        }
+        // On the river every equity is decided
+        let histogram = progression[1].get_equity_histogram(0, 10);
+        assert_eq!(histogram[0] + histogram[9], 45 * 44 / 2);
+    }
+
+    #[test]
+    fn can_count_blocked_streets() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhAc").unwrap(),
+                Seat::from_str("AA").unwrap(),
+            ],
+            Card::from_cards_str("2c7s9d").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let breakdown = table.get_street_breakdown(100, 1000, None).unwrap();
+
+        // The other aces are the only combo of the range
+        assert_eq!(breakdown.blocked, 2);
+        assert_eq!(breakdown.street_results.len(), 47 - 2);
+    }
+
+    #[test]
+    fn cannot_average_equity_without_streets() {
+        let breakdown = StreetBreakdown {
+            board_cards: 3,
+            street_results: vec![],
+            blocked: 0,
+            approximate: false,
+            time_in_ms: 0,
+        };
+
+        assert_eq!(breakdown.get_average_equity(0), None);
+        assert_eq!(breakdown.get_equity_histogram(0, 10), vec![0; 10]);
+    }
+
+    #[test]
+    fn can_get_sampled_flop_breakdown() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhKh").unwrap(),
+                Seat::from_str("QQ+").unwrap(),
+            ],
+            vec![],
+            vec![],
+        )
+        .unwrap();
+        let breakdown = table.get_street_breakdown(20, 100, Some(4)).unwrap();
+
+        assert!(breakdown.approximate);
+        assert!(breakdown.street_results.len() <= 20);
+        for street_result in &breakdown.street_results {
+            assert_eq!(street_result.cards.len(), 3);
+            assert!((street_result.equities.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn cannot_get_breakdown_without_next_street() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhAd").unwrap(),
+                Seat::from_str("KhKd").unwrap(),
+            ],
+            Card::from_cards_str("2c7s9d3cTs").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(
+            table.get_street_breakdown(100, 100, None).unwrap_err(),
+            Error::NoNextStreet
+        );
+
+        let table = Table::new(GameType::Razz, vec![Seat::Hand(vec![]); 2], vec![], vec![]);
+        assert_eq!(
+            table
+                .unwrap()
+                .get_street_breakdown(100, 100, None)
+                .unwrap_err(),
+            Error::NoNextStreet
+        );
+    }
+
+    #[test]
+    fn can_get_outs() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhKh").unwrap(),
+                Seat::from_str("QcQd").unwrap(),
+            ],
+            Card::from_cards_str("Qh7h2c3s").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let outs = table.get_outs().unwrap();
+
+        assert_eq!(outs.len(), 44);
+        // Hearts pairing the board fill the queens up
+        let hero_outs: Vec<String> = outs
+            .iter()
+            .filter(|out| out.winners == vec![0])
+            .map(|out| out.card.to_string())
+            .sorted()
+            .collect();
+        assert_eq!(hero_outs, vec!["4h", "5h", "6h", "8h", "9h", "Jh", "Th"]);
+        let out = outs
+            .iter()
+            .find(|out| out.card == Card::from_str("2h").unwrap())
+            .unwrap();
+        assert_eq!(out.winners, vec![1]);
+        assert_eq!(out.pot_shares, vec![0.0, 1.0]);
+        assert_eq!(
+            out.hand_combinations,
+            vec![HandCombination::Flush, HandCombination::FullHouse]
+        );
+    }
+
+    #[test]
+    fn cannot_get_outs_of_unknown_cards() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhKh").unwrap(),
+                Seat::from_str("QQ").unwrap(),
+            ],
+            Card::from_cards_str("Jh7h2c3s").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(table.get_outs().unwrap_err(), Error::UnknownCards);
+
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhKh").unwrap(),
+                Seat::from_str("Qc").unwrap(),
+            ],
+            Card::from_cards_str("Jh7h2c").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        assert_eq!(
+            table.get_outs().unwrap_err(),
+            Error::WrongMissingBoardCards {
+                expected: 1,
+                missing: 2
+            }
+        );
+    }
+
+    #[test]
+    fn can_get_outs_by_equity() {
+        let table = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhAd").unwrap(),
+                Seat::from_str("KhKd").unwrap(),
+            ],
+            Card::from_cards_str("2c7s9d").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let groups = table.get_outs_by_equity(1000, None).unwrap();
+
+        assert_eq!(
+            groups
+                .iter()
+                .map(|group| (group.equities.clone(), group.cards.len()))
+                .collect::<Vec<(Vec<f64>, usize)>>(),
+            vec![
+                (vec![1.0, 0.0], 2),
+                (vec![42.0 / 44.0, 2.0 / 44.0], 41),
+                (vec![2.0 / 44.0, 42.0 / 44.0], 2),
+            ]
+        );
+        assert_eq!(groups[2].cards, Card::from_cards_str("KcKs").unwrap());
+    }
+
+    #[test]
+    fn can_get_hand_strength_on_river() {
+        let potential = Table::new(
+            GameType::TexasHoldem,
+            vec![Seat::from_str("AsAd").unwrap(), Seat::from_str("").unwrap()],
+            Card::from_cards_str("AhKh7c2d9s").unwrap(),
+            vec![],
+        )
+        .unwrap()
+        .get_hand_potential(10000, 10000, Some(0))
+        .unwrap();
+
+        assert_eq!(potential.holdings, 45 * 44 / 2);
+        assert_eq!(potential.get_hand_strength(), 1.0);
+        assert_eq!(potential.positive_potential, 0.0);
+        assert_eq!(potential.negative_potential, 0.0);
+        assert!(!potential.approximate);
+    }
+
+    #[test]
+    fn can_get_hand_potentials() {
+        // Seven hearts and six straight cards beat the kings, the 2h and Kh fill them up
+        let potential = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("8h9h").unwrap(),
+                Seat::from_str("KK").unwrap(),
+            ],
+            Card::from_cards_str("6h7hKc2d").unwrap(),
+            vec![],
+        )
+        .unwrap()
+        .get_hand_potential(10000, 10000, Some(0))
+        .unwrap();
+        assert_eq!(potential.holdings, 3);
+        assert_eq!(potential.behind, 1.0);
+        assert!((potential.positive_potential - 13.0 / 44.0).abs() < 1e-9);
+        assert_eq!(potential.negative_potential, 0.0);
+
+        let potential = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("KsKd").unwrap(),
+                Seat::from_str("8h9h").unwrap(),
+            ],
+            Card::from_cards_str("6h7hKc2d").unwrap(),
+            vec![],
+        )
+        .unwrap()
+        .get_hand_potential(10000, 10000, Some(0))
+        .unwrap();
+        assert_eq!(potential.ahead, 1.0);
+        assert!((potential.negative_potential - 13.0 / 44.0).abs() < 1e-9);
+        assert!((potential.get_effective_hand_strength() - 31.0 / 44.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn can_get_hand_strength_against_range() {
+        // Ahead of the queens, tied with the other ace kings and behind the set
+        let potential = Table::new(
+            GameType::TexasHoldem,
+            vec![
+                Seat::from_str("AhKs").unwrap(),
+                Seat::from_str("AK,77,QQ").unwrap(),
+            ],
+            Card::from_cards_str("Kc7h2d").unwrap(),
+            vec![],
+        )
+        .unwrap()
+        .get_hand_potential(10000, 10000, Some(0))
+        .unwrap();
+
+        assert_eq!(potential.holdings, 6 + 3 + 6);
+        assert!((potential.ahead - 0.4).abs() < 1e-9);
+        assert!((potential.tied - 0.4).abs() < 1e-9);
+        assert!((potential.behind - 0.2).abs() < 1e-9);
+        assert!((potential.get_hand_strength() - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn can_sample_hand_potential_holdings() {
+        let table = Table::new(
+            GameType::Omaha6,
+            vec![
+                Seat::from_str("AhKhQhJhTh9h").unwrap(),
+                Seat::from_str("").unwrap(),
+            ],
+            Card::from_cards_str("8h7d2s").unwrap(),
+            vec![],
+        )
+        .unwrap();
+        let potential = table.get_hand_potential(20, 100, Some(0)).unwrap();
+
+        assert_eq!(potential.holdings, 6096454);
+        assert!(potential.approximate);
+        assert!((potential.ahead + potential.tied + potential.behind - 1.0).abs() < 1e-9);
+        assert_eq!(
+            table.get_hand_potential(20, 100, Some(0)).unwrap().ahead,
+            potential.ahead
+        );
+
+        let potential = table.get_hand_potential(0, 100, Some(0)).unwrap();
+        assert_eq!(potential.get_hand_strength(), 0.0);
+        assert_eq!(potential.get_effective_hand_strength(), 0.0);
+    }
+
+    #[test]
+    fn cannot_get_hand_potential() {
+        let get_error = |seats: &[&str], board: &str| {
+            Table::new(
+                GameType::TexasHoldem,
+                seats.iter().map(|s| Seat::from_str(s).unwrap()).collect(),
+                Card::from_cards_str(board).unwrap(),
+                vec![],
+            )
+            .unwrap()
+            .get_hand_potential(10000, 10000, None)
+            .unwrap_err()
+        };
+
+        assert_eq!(get_error(&["AhKh", "", ""], "2c3d4s"), Error::NotHeadsUp(3));
+        assert_eq!(get_error(&["Ah", ""], "2c3d4s"), Error::UnknownCards);
+        assert_eq!(get_error(&["AhKh", ""], ""), Error::NoFlop(0));
     }
 }
@@ -0,0 +1,308 @@
+use itertools::Itertools;
+
+use crate::{
+    card::{Card, Rank},
+    game::{get_two_card_hand_strengths, HandCombination},
+    hand::validate_cards,
+    rules::Rules,
+    Error, GameType,
+};
+
+/// How many cards of a suit the board shows.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Suits {
+    /// No two cards share a suit.
+    Rainbow,
+    /// Two cards of a suit, no flush is possible yet.
+    TwoTone,
+    /// Three or more cards of a suit on a turn or river with other suits, a
+    /// flush is possible.
+    FlushPossible,
+    /// Every card of the board has the same suit.
+    Monotone,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Pairing {
+    Unpaired,
+    Paired,
+    TwoPaired,
+    Trips,
+    FullHouse,
+    Quads,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Connectedness {
+    /// No straight is possible.
+    Disconnected,
+    /// A straight is possible, with gaps between the board cards making it.
+    Gapped,
+    /// Three ranks in a row, a straight is possible.
+    Connected,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoardTexture {
+    pub suits: Suits,
+    pub pairing: Pairing,
+    pub connectedness: Connectedness,
+    /// The highest card is a ten or better.
+    pub high: bool,
+    /// Cards are to come and the board gives flush draws, with two or more cards
+    /// of a suit, or open ended straight draws.
+    pub dynamic: bool,
+    /// Hand combinations some hole cards make on the board, the nuts first.
+    pub possible_hands: Vec<HandCombination>,
+}
+
+impl BoardTexture {
+    /// Best hand combination any hole cards make on the board.
+    pub fn nuts(&self) -> HandCombination {
+        self.possible_hands[0]
+    }
+
+    pub fn is_possible(&self, hand_combination: HandCombination) -> bool {
+        self.possible_hands.contains(&hand_combination)
+    }
+}
+
+/// Classifies a flop, turn or river for Hold'em, short deck and Omaha games.
+/// Omaha hands have to use two hole cards and three board cards.
+pub fn get_board_texture(game_type: GameType, board: &[Card]) -> Result<BoardTexture, Error> {
+    let rules = Rules::new(game_type);
+    validate_cards(game_type, &rules, &[], board)?;
+    if board.len() < 3 {
+        return Err(Error::NoFlop(board.len()));
+    }
+
+    let suit_counts: Vec<usize> = board
+        .iter()
+        .map(|c| c.suit)
+        .counts()
+        .into_values()
+        .sorted()
+        .rev()
+        .collect();
+    let rank_counts: Vec<usize> = board
+        .iter()
+        .map(|c| c.rank)
+        .counts()
+        .into_values()
+        .sorted()
+        .rev()
+        .collect();
+    let board_ranks: Vec<Rank> = board.iter().map(|c| c.rank).collect();
+    let straight_order = rules.get_straight_order();
+    let ranks_in_row = |size: usize| -> usize {
+        straight_order
+            .windows(size)
+            .map(|ranks| ranks.iter().filter(|r| board_ranks.contains(r)).count())
+            .max()
+            .unwrap_or(0)
+    };
+
+    let suits = match suit_counts[0] {
+        1 => Suits::Rainbow,
+        2 => Suits::TwoTone,
+        count if count < board.len() => Suits::FlushPossible,
+        _ => Suits::Monotone,
+    };
+    let pairing = match rank_counts[..] {
+        [4, ..] => Pairing::Quads,
+        [3, 2, ..] => Pairing::FullHouse,
+        [3, ..] => Pairing::Trips,
+        [2, 2, ..] => Pairing::TwoPaired,
+        [2, ..] => Pairing::Paired,
+        _ => Pairing::Unpaired,
+    };
+    let connectedness = if ranks_in_row(3) == 3 {
+        Connectedness::Connected
+    } else if ranks_in_row(5) >= 3 {
+        Connectedness::Gapped
+    } else {
+        Connectedness::Disconnected
+    };
+    let high = board_ranks.iter().any(|r| *r >= Rank::Ten);
+    // Two suited cards leave a flush draw, two cards in four ranks an open ended one
+    let dynamic =
+        board.len() < game_type.board_cards() && (suits != Suits::Rainbow || ranks_in_row(4) >= 2);
+
+    Ok(BoardTexture {
+        suits,
+        pairing,
+        connectedness,
+        high,
+        dynamic,
+        possible_hands: get_possible_hands(game_type, &rules, board),
+    })
+}
+
+fn get_possible_hands(game_type: GameType, rules: &Rules, board: &[Card]) -> Vec<HandCombination> {
+    let unused_cards = rules.get_unused_cards(&[], board);
+    let hand_combinations: Vec<HandCombination> =
+        get_two_card_hand_strengths(game_type, rules, &unused_cards, board)
+            .into_iter()
+            .map(|(_, hand_strength)| hand_strength.hand_combination)
+            .unique()
+            .collect();
+    rules
+        .ranking
+        .iter()
+        .filter(|hc| hand_combinations.contains(hc))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_get_monotone_flop_texture() {
+        let texture = get_board_texture(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("AhKh7h").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(texture.suits, Suits::Monotone);
+        assert_eq!(texture.pairing, Pairing::Unpaired);
+        assert_eq!(texture.connectedness, Connectedness::Disconnected);
+        assert!(texture.high);
+        assert!(texture.dynamic);
+        assert_eq!(
+            texture.possible_hands,
+            vec![
+                HandCombination::Flush,
+                HandCombination::TreeOfAKind,
+                HandCombination::TwoPairs,
+                HandCombination::OnePair,
+                HandCombination::HighCards,
+            ]
+        );
+        assert_eq!(texture.nuts(), HandCombination::Flush);
+    }
+
+    #[test]
+    fn can_get_connected_and_static_textures() {
+        let texture = get_board_texture(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("9s8d7c").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(texture.suits, Suits::Rainbow);
+        assert_eq!(texture.connectedness, Connectedness::Connected);
+        assert!(!texture.high);
+        assert!(texture.dynamic);
+        assert_eq!(texture.nuts(), HandCombination::Straight);
+        assert!(!texture.is_possible(HandCombination::Flush));
+
+        let texture = get_board_texture(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("Kh7d2c").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(texture.connectedness, Connectedness::Disconnected);
+        assert!(!texture.dynamic);
+        assert_eq!(texture.nuts(), HandCombination::TreeOfAKind);
+
+        let texture = get_board_texture(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("Ks9s5d").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(texture.suits, Suits::TwoTone);
+        assert_eq!(texture.connectedness, Connectedness::Disconnected);
+        assert!(texture.dynamic);
+    }
+
+    #[test]
+    fn can_get_turn_suits() {
+        let texture = get_board_texture(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("Kh7h2h3c").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(texture.suits, Suits::FlushPossible);
+        assert!(texture.dynamic);
+        assert!(texture.is_possible(HandCombination::Flush));
+
+        let texture = get_board_texture(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("Kh7h2h3h").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(texture.suits, Suits::Monotone);
+    }
+
+    #[test]
+    fn can_get_paired_river_texture() {
+        let texture = get_board_texture(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("QsQd6h5c4h").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(texture.pairing, Pairing::Paired);
+        assert_eq!(texture.connectedness, Connectedness::Connected);
+        assert!(!texture.dynamic);
+        assert_eq!(texture.nuts(), HandCombination::Quads);
+        assert!(texture.is_possible(HandCombination::Straight));
+        assert!(!texture.is_possible(HandCombination::HighCards));
+    }
+
+    #[test]
+    fn can_get_short_deck_texture() {
+        // A-6-7 is in a row with the low straight of the short deck
+        let texture = get_board_texture(
+            GameType::ShortdeckHoldem,
+            &Card::from_cards_str("Ah6d7c").unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(texture.connectedness, Connectedness::Connected);
+        assert_eq!(texture.nuts(), HandCombination::Straight);
+    }
+
+    #[test]
+    fn can_get_omaha_texture() {
+        // Four of a kind on the board plays as trips with two hole cards
+        let texture = get_board_texture(
+            GameType::Omaha,
+            &Card::from_cards_str("9s9d9h9c2s").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(texture.pairing, Pairing::Quads);
+        assert_eq!(texture.nuts(), HandCombination::FullHouse);
+        assert!(!texture.is_possible(HandCombination::Quads));
+
+        let texture = get_board_texture(
+            GameType::TexasHoldem,
+            &Card::from_cards_str("9s9d9h9c2s").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(texture.possible_hands, vec![HandCombination::Quads]);
+    }
+
+    #[test]
+    fn cannot_get_texture_without_flop() {
+        assert_eq!(
+            get_board_texture(
+                GameType::TexasHoldem,
+                &Card::from_cards_str("AhKh").unwrap()
+            ),
+            Err(Error::NoFlop(2))
+        );
+        assert_eq!(
+            get_board_texture(
+                GameType::SevenCardStud,
+                &Card::from_cards_str("AhKh7h").unwrap()
+            ),
+            Err(Error::TooManyBoardCards {
+                game_type: GameType::SevenCardStud,
+                cards: 3
+            })
+        );
+    }
+}